@@ -0,0 +1,324 @@
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+};
+
+use gherkin::{
+    Background, DataTable, ExamplesLoader, Feature, FsExamplesLoader, KeywordType, Location,
+    ParserOptions, Scenario, ScenarioOutline, Step, StepData, StepType, TaggedScenarios,
+};
+use proc_macro2::TokenStream;
+use proc_macro_error::abort;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    LitStr, Token,
+};
+
+/// The arguments of `include_feature!`: the path to the feature file,
+/// optionally followed by `crate = <path>`, the path to the `gherkin` crate.
+pub struct Input {
+    path: LitStr,
+    gherkin: Option<syn::Path>,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse()?;
+
+        let mut gherkin = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            input.parse::<Token![crate]>()?;
+            input.parse::<Token![=]>()?;
+            gherkin = Some(input.parse()?);
+            input.parse::<Option<Token![,]>>()?;
+        }
+
+        Ok(Self { path, gherkin })
+    }
+}
+
+/// Parse the feature file at `path`, relative to the directory of the
+/// manifest of the crate being compiled, into an expression that constructs
+/// a `gherkin::StaticFeature`.
+pub fn include_feature(input: Input) -> TokenStream {
+    let Input { path, gherkin } = input;
+    let gherkin = match gherkin {
+        Some(gherkin) => quote!(#gherkin),
+        None => quote!(::cucumber::gherkin),
+    };
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = PathBuf::from(manifest_dir).join(path.value());
+
+    let loader = SourceLoader::new(&full_path);
+    let feature = match parse(&path.value(), &full_path, &loader) {
+        Ok(feature) => feature,
+        Err(e) => abort!(path.span(), "{}", e),
+    };
+
+    let full_path = full_path.to_string_lossy();
+    let sources = loader.loaded.into_inner();
+    let sources = sources.iter().map(|p| p.to_string_lossy());
+    let feature = feature_tokens(&gherkin, &feature);
+
+    quote! {
+        {
+            // Rebuild when the feature file or its examples change.
+            const _: &str = ::core::include_str!(#full_path);
+            #(const _: &str = ::core::include_str!(#sources);)*
+            #gherkin::StaticFeature::new(#path, || #feature)
+        }
+    }
+}
+
+/// Read and parse the feature file at `full_path`, given as `path`. The error
+/// is the message of the compile error.
+fn parse(path: &str, full_path: &Path, loader: &SourceLoader) -> Result<Feature, String> {
+    let contents = std::fs::read_to_string(full_path)
+        .map_err(|e| format!("Failed to read {}: {}", full_path.display(), e))?;
+
+    let options = ParserOptions::strict().examples_loader(loader);
+    let parsed = if path.ends_with(".md") {
+        options.parse_markdown(&contents)
+    } else {
+        options.parse(&contents)
+    };
+
+    parsed.map_err(|e| format!("{}:{}:{}: {}", path, e.location.line, e.location.column, e))
+}
+
+/// Loads `@source` examples with a `gherkin::FsExamplesLoader`, and records
+/// the files it read.
+struct SourceLoader {
+    fs: FsExamplesLoader,
+    loaded: RefCell<Vec<PathBuf>>,
+}
+
+impl SourceLoader {
+    fn new(feature_path: &Path) -> Self {
+        Self {
+            fs: FsExamplesLoader::for_feature(feature_path),
+            loaded: RefCell::default(),
+        }
+    }
+}
+
+impl ExamplesLoader for SourceLoader {
+    fn load(&self, source: &str) -> Result<DataTable, String> {
+        let table = self.fs.load(source)?;
+        self.loaded.borrow_mut().push(self.fs.path(source)?);
+        Ok(table)
+    }
+}
+
+fn string(value: &str) -> TokenStream {
+    quote!(::core::convert::From::from(#value))
+}
+
+fn option_string(value: &Option<String>) -> TokenStream {
+    match value {
+        Some(value) => {
+            let value = string(value);
+            quote!(::core::option::Option::Some(#value))
+        }
+        None => quote!(::core::option::Option::None),
+    }
+}
+
+fn vec(items: impl Iterator<Item = TokenStream>) -> TokenStream {
+    quote!(::core::convert::From::from([#(#items),*]))
+}
+
+fn strings(values: &[String]) -> TokenStream {
+    vec(values.iter().map(|v| string(v)))
+}
+
+fn location(gherkin: &TokenStream, location: Location) -> TokenStream {
+    let Location { line, column } = location;
+    quote!(#gherkin::Location::new(#line, #column))
+}
+
+fn table(header: &[String], rows: &[Vec<String>]) -> (TokenStream, TokenStream) {
+    (strings(header), vec(rows.iter().map(|row| strings(row))))
+}
+
+fn step(gherkin: &TokenStream, step: &Step) -> TokenStream {
+    let ty = match step.ty {
+        StepType::Given => quote!(Given),
+        StepType::When => quote!(When),
+        StepType::Then => quote!(Then),
+        StepType::And => quote!(And),
+        StepType::But => quote!(But),
+        StepType::Asterisk => quote!(Asterisk),
+    };
+    let keyword_type = match step.keyword_type {
+        KeywordType::Context => quote!(Context),
+        KeywordType::Action => quote!(Action),
+        KeywordType::Outcome => quote!(Outcome),
+        KeywordType::Unknown => quote!(Unknown),
+    };
+    let description = string(&step.description);
+    let data = match &step.data {
+        Some(StepData::DocString(doc_string)) => {
+            let doc_string = string(doc_string);
+            quote!(::core::option::Option::Some(#gherkin::StepData::DocString(#doc_string)))
+        }
+        Some(StepData::DataTable(data_table)) => {
+            let (header, rows) = table(data_table.header(), data_table.rows());
+            quote! {
+                ::core::option::Option::Some(#gherkin::StepData::DataTable(
+                    ::core::option::Option::unwrap(#gherkin::DataTable::new_populated(#header, #rows))
+                ))
+            }
+        }
+        None => quote!(::core::option::Option::None),
+    };
+    let location = location(gherkin, step.location);
+
+    quote! {
+        #gherkin::Step {
+            ty: #gherkin::StepType::#ty,
+            keyword_type: #gherkin::KeywordType::#keyword_type,
+            description: #description,
+            data: #data,
+            location: #location,
+        }
+    }
+}
+
+fn steps(gherkin: &TokenStream, steps: &[Step]) -> TokenStream {
+    vec(steps.iter().map(|s| step(gherkin, s)))
+}
+
+fn background(gherkin: &TokenStream, background: &Background) -> TokenStream {
+    let name = option_string(&background.name);
+    let description = option_string(&background.description);
+    let steps = steps(gherkin, &background.steps);
+    let location = location(gherkin, background.location);
+
+    quote! {
+        #gherkin::Background {
+            name: #name,
+            description: #description,
+            steps: #steps,
+            location: #location,
+        }
+    }
+}
+
+fn scenario(gherkin: &TokenStream, scenario: &Scenario) -> TokenStream {
+    let tags = strings(&scenario.tags);
+    let name = option_string(&scenario.name);
+    let description = option_string(&scenario.description);
+    let steps = steps(gherkin, &scenario.steps);
+    let location = location(gherkin, scenario.location);
+
+    quote! {
+        #gherkin::Scenario {
+            tags: #tags,
+            name: #name,
+            description: #description,
+            steps: #steps,
+            location: #location,
+        }
+    }
+}
+
+fn examples(gherkin: &TokenStream, examples: &TaggedScenarios) -> TokenStream {
+    let tags = strings(examples.tags());
+    let (placeholders, values) = table(examples.placeholders(), examples.values());
+    let name = option_string(&examples.name().map(String::from));
+    let description = option_string(&examples.description().map(String::from));
+    let row_locations = vec(examples
+        .row_locations()
+        .iter()
+        .copied()
+        .map(|l| location(gherkin, l)));
+    let location = location(gherkin, examples.location());
+
+    quote! {
+        {
+            let mut examples = ::core::result::Result::unwrap(
+                #gherkin::TaggedScenarios::new(#tags, #placeholders, #values)
+            );
+            *examples.name_mut() = #name;
+            *examples.description_mut() = #description;
+            *examples.location_mut() = #location;
+            *examples.row_locations_mut() = #row_locations;
+            examples
+        }
+    }
+}
+
+fn scenario_outline(gherkin: &TokenStream, outline: &ScenarioOutline) -> TokenStream {
+    let tags = strings(&outline.tags);
+    let name = option_string(&outline.name);
+    let description = option_string(&outline.description);
+    let steps = steps(gherkin, &outline.steps);
+    let scenarios = vec(outline.scenarios.iter().map(|e| examples(gherkin, e)));
+    let location = location(gherkin, outline.location);
+
+    quote! {
+        #gherkin::ScenarioOutline {
+            tags: #tags,
+            name: #name,
+            description: #description,
+            steps: #steps,
+            scenarios: #scenarios,
+            location: #location,
+        }
+    }
+}
+
+fn feature_tokens(gherkin: &TokenStream, feature: &Feature) -> TokenStream {
+    let tags = strings(&feature.tags);
+    let name = option_string(&feature.name);
+    let description = option_string(&feature.description);
+    let background = match &feature.background {
+        Some(b) => {
+            let background = background(gherkin, b);
+            quote!(::core::option::Option::Some(#background))
+        }
+        None => quote!(::core::option::Option::None),
+    };
+    let scenarios = vec(feature.scenarios.iter().map(|s| scenario(gherkin, s)));
+    let scenario_outlines = vec(feature
+        .scenario_outlines
+        .iter()
+        .map(|o| scenario_outline(gherkin, o)));
+    let location = location(gherkin, feature.location);
+
+    quote! {
+        #gherkin::Feature {
+            tags: #tags,
+            name: #name,
+            description: #description,
+            background: #background,
+            scenarios: #scenarios,
+            scenario_outlines: #scenario_outlines,
+            location: #location,
+        }
+    }
+}
+
+#[test]
+fn parse_errors() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let path = "tests/features/no_steps.feature";
+    let full_path = manifest_dir.join(path);
+
+    let error = parse(path, &full_path, &SourceLoader::new(&full_path)).unwrap_err();
+    assert!(
+        error.starts_with("tests/features/no_steps.feature:2:3: "),
+        "{error}"
+    );
+
+    let error = parse(
+        "missing.feature",
+        &manifest_dir.join("missing.feature"),
+        &SourceLoader::new(&full_path),
+    )
+    .unwrap_err();
+    assert!(error.starts_with("Failed to read "), "{error}");
+}
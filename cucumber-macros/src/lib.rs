@@ -3,6 +3,54 @@ use proc_macro2::Group;
 use proc_macro_error::*;
 use syn::{spanned::Spanned, Attribute, ImplItem, ItemImpl, Lit, LitStr};
 
+mod include_feature;
+
+/// Parse a feature file at compile time into a `gherkin::StaticFeature`, so
+/// that the feature file is not needed at run time.
+///
+/// The path is relative to the directory containing the `Cargo.toml` of
+/// the crate being compiled. Files ending in `.md` are parsed as Markdown
+/// with Gherkin, and `@source` examples are loaded relative to the feature
+/// file. Parse errors are reported as compile errors, with the location in
+/// the feature file.
+///
+/// The expansion is const-constructible, so it can initialize a `static`
+/// without `std`:
+///
+/// ```
+/// static FEATURE: cucumber::gherkin::StaticFeature =
+///     cucumber::include_feature!("../cucumber/tests/features/buying.feature");
+///
+/// assert_eq!(FEATURE.feature().total_scenario_count(), 3);
+/// ```
+///
+/// The expansion refers to the `gherkin` crate as `::cucumber::gherkin`.
+/// Crates that depend on `gherkin` directly pass its path with `crate`:
+///
+/// ```
+/// # use cucumber::{gherkin, include_feature};
+/// let feature: gherkin::StaticFeature =
+///     include_feature!("../cucumber/tests/features/buying.feature", crate = gherkin);
+/// ```
+///
+/// A feature that cannot be parsed does not compile:
+///
+/// ```compile_fail
+/// let feature = cucumber::include_feature!("tests/features/no_steps.feature");
+/// ```
+#[proc_macro]
+#[proc_macro_error]
+pub fn include_feature(input: TokenStream) -> TokenStream {
+    let input = match syn::parse(input) {
+        Ok(input) => input,
+        Err(_) => abort_call_site!(
+            "`include_feature!` takes the path to a feature file, optionally followed by `crate = <path to gherkin>`"
+        ),
+    };
+
+    include_feature::include_feature(input).into()
+}
+
 #[proc_macro_attribute]
 #[proc_macro_error]
 pub fn cucumber_world(_attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -42,11 +90,11 @@ pub fn cucumber_world(_attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     });
 
-    let given = fns
+    let _given = fns
         .clone()
         .filter_map(|f| find_literal_attr("given", &f.attrs).map(|str| (str, f)));
 
-    let whens = fns
+    let _whens = fns
         .clone()
         .filter_map(|f| find_literal_attr("when", &f.attrs).map(|str| (str, f)));
 
@@ -54,20 +102,18 @@ pub fn cucumber_world(_attr: TokenStream, item: TokenStream) -> TokenStream {
         let new_attrs = f
             .attrs
             .iter()
-            .cloned()
-            .filter(|a| !attr_with_name("given", a) && !attr_with_name("when", a));
+            .filter(|a| !attr_with_name("given", a) && !attr_with_name("when", a))
+            .cloned();
         f.attrs = new_attrs.collect();
         f
     });
 
-    let result = quote::quote! {
+    quote::quote! {
         impl #impl_gen #impl_ident #ty_gen #where_clause {
             #(#fns_stripped_attrs)*
         }
     }
-    .into();
-
-    result
+    .into()
 }
 
 fn attr_with_name(name: &str, attr: &Attribute) -> bool {
@@ -76,7 +122,7 @@ fn attr_with_name(name: &str, attr: &Attribute) -> bool {
             .segments
             .iter()
             .next()
-            .map(|p| p.ident.to_string() == name)
+            .map(|p| p.ident == name)
             .unwrap_or(false)
     } else {
         false
@@ -0,0 +1,2 @@
+Feature: no steps
+  Scenario: nothing happens
@@ -1,4 +1,6 @@
-pub use cucumber_macros::cucumber_world;
+pub use cucumber_macros::{cucumber_world, include_feature};
+
+pub use gherkin;
 
 pub enum Error {}
 
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use cucumber::cucumber_world;
 
 mod inner {
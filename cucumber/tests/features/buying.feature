@@ -0,0 +1,28 @@
+@shop
+Feature: Buying
+  Customers buy things.
+
+  Background: a shop
+    Given a shop
+    And it is open
+
+  @fast
+  Scenario: paying
+    Given the following prices
+      | item   | price |
+      | banana | 1     |
+    When I pay
+    Then the receipt reads
+      """
+      1 banana
+      """
+
+  Scenario Outline: buying many
+    Given <count> items
+
+    @small
+    Examples: few
+      Only a few.
+      | count |
+      | 1     |
+      | 2     |
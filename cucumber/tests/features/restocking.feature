@@ -0,0 +1,6 @@
+Feature: Restocking
+  Scenario Outline: restocking
+    Given <count> <item>s
+
+    @source(data/stock.csv)
+    Examples:
@@ -0,0 +1,22 @@
+use cucumber::{gherkin, include_feature};
+
+static BUYING: gherkin::StaticFeature = include_feature!("tests/features/buying.feature");
+
+#[test]
+fn include_feature() {
+    let feature = BUYING.feature();
+    let parsed = gherkin::Parser::parse_feature(include_str!("features/buying.feature")).unwrap();
+
+    assert_eq!(BUYING.path(), "tests/features/buying.feature");
+    assert_eq!(feature, parsed);
+    assert_eq!(feature.total_scenario_count(), 3);
+}
+
+#[test]
+fn source_examples() {
+    let feature = include_feature!("tests/features/restocking.feature", crate = gherkin).feature();
+    let examples = &feature.scenario_outlines[0].scenarios[0];
+
+    assert_eq!(examples.placeholders(), &["item", "count"]);
+    assert_eq!(examples.values(), &[["banana", "3"], ["apple", "5"]]);
+}
@@ -0,0 +1,128 @@
+//! Compares the memory and time of expanding a large generated corpus with
+//! plain features against interned ones.
+//!
+//! Run with `cargo bench -p gherkin --bench intern`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Write,
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use gherkin::{intern::Interner, Feature};
+
+/// Tracks the amount of bytes currently allocated.
+struct Counting;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+const FEATURES: usize = 200;
+const OUTLINES: usize = 10;
+const ROWS: usize = 50;
+
+/// A feature with outlines that share most of their steps, the way the
+/// features of a real suite do.
+fn generate(idx: usize) -> String {
+    let mut source = format!("@generated\nFeature: Shop {idx}\n");
+    for outline in 0..OUTLINES {
+        writeln!(
+            source,
+            r#"
+  @checkout
+  Scenario Outline: Buying {outline}
+    Given a shop with the default catalogue
+    And a customer who is logged in
+    And an empty shopping cart
+    When the customer buys <count> <item>
+    Then the cart contains <count> <item>
+    And the total is computed with the default prices
+    And the receipt reads
+      """
+      Thank you for shopping with us
+      """
+
+    Examples:
+      | count | item   |"#
+        )
+        .unwrap();
+        for row in 0..ROWS {
+            writeln!(source, "      | {row:<5} | apples |").unwrap();
+        }
+    }
+    source
+}
+
+/// Measure the bytes still allocated by, and the time taken by, `f`.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, usize, Duration) {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let result = black_box(f());
+    let elapsed = start.elapsed();
+    (result, ALLOCATED.load(Ordering::Relaxed) - before, elapsed)
+}
+
+fn report(name: &str, bytes: usize, elapsed: Duration) {
+    println!(
+        "{name:<10} {:>10.1} MiB {:>10.1} ms",
+        bytes as f64 / (1024.0 * 1024.0),
+        elapsed.as_secs_f64() * 1000.0
+    );
+}
+
+fn main() {
+    let features: Vec<Feature> = (0..FEATURES)
+        .map(|idx| gherkin::Parser::parse_feature(&generate(idx)).unwrap())
+        .collect();
+    let scenarios = FEATURES * OUTLINES * ROWS;
+    println!("{FEATURES} features, {scenarios} expanded scenarios\n");
+
+    let (plain, plain_bytes, plain_time) = measure(|| {
+        features
+            .iter()
+            .map(|f| f.scenarios().collect::<Vec<_>>())
+            .collect::<Vec<_>>()
+    });
+
+    let (interned, interned_bytes, interned_time) = measure(|| {
+        let mut interner = Interner::default();
+        features
+            .iter()
+            .map(|f| {
+                let feature = interner.feature(f);
+                let scenarios = feature.scenarios().collect::<Vec<_>>();
+                (feature, scenarios)
+            })
+            .collect::<Vec<_>>()
+    });
+
+    assert_eq!(plain.iter().map(Vec::len).sum::<usize>(), scenarios);
+    assert_eq!(
+        interned.iter().map(|(_, s)| s.len()).sum::<usize>(),
+        scenarios
+    );
+
+    report("plain", plain_bytes, plain_time);
+    report("interned", interned_bytes, interned_time);
+    println!(
+        "\n{:.1}x less memory, {:.1}x faster",
+        plain_bytes as f64 / interned_bytes as f64,
+        plain_time.as_secs_f64() / interned_time.as_secs_f64()
+    );
+}
@@ -1,7 +1,14 @@
 use std::{fs::File, io::Read};
 
 use anyhow::Error;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use gherkin::render::{self, Style};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Highlight {
+    Ansi,
+    Html,
+}
 
 #[derive(Parser)]
 pub struct Cli {
@@ -9,19 +16,32 @@ pub struct Cli {
     #[clap(long, short)]
     pub print: bool,
 
+    /// Print the parsed feature as highlighted Gherkin.
+    #[clap(long, value_enum)]
+    pub highlight: Option<Highlight>,
+
     pub file: String,
 }
 
-fn parse_feature(name: &str, mut file: File, print: bool) -> Result<(), Error> {
+fn parse_feature(name: &str, mut file: File, cli: &Cli) -> Result<(), Error> {
     let mut str = String::with_capacity(131072);
     file.read_to_string(&mut str)?;
 
-    let feature = gherkin::Parser::parse_feature(&str).expect(&format!("Failed for {:?}", name));
+    let feature = gherkin::Parser::parse_feature(&str)
+        .unwrap_or_else(|e| panic!("Failed for {name:?}: {e}"));
 
-    if print {
+    if cli.print {
         println!("{feature:#?}");
     }
 
+    if let Some(highlight) = cli.highlight {
+        let style = match highlight {
+            Highlight::Ansi => Style::Ansi,
+            Highlight::Html => Style::Html,
+        };
+        println!("{}", render::render(&feature, style));
+    }
+
     let scenario_count = feature.scenarios().count();
     let computed_scenario_count = feature.total_scenario_count();
     assert_eq!(scenario_count, computed_scenario_count);
@@ -34,7 +54,7 @@ fn main() -> Result<(), Error> {
     let cli = Cli::parse();
 
     let file = File::open(&cli.file)?;
-    parse_feature(&cli.file, file, cli.print)?;
+    parse_feature(&cli.file, file, &cli)?;
 
     Ok(())
 }
@@ -0,0 +1,42 @@
+//! Helpers shared by the command line tools.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Error};
+use gherkin::{Feature, FsExamplesLoader, ParseError, ParserOptions};
+
+/// Add `path` to `features` if it is a file, or all `.feature` files in it,
+/// recursively and in order, if it is a directory.
+pub fn collect_features(path: &Path, features: &mut Vec<PathBuf>) -> Result<(), Error> {
+    if !path.is_dir() {
+        features.push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let mut entries = std::fs::read_dir(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    for entry in entries {
+        if entry.is_dir() || entry.extension().is_some_and(|e| e == "feature") {
+            collect_features(&entry, features)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Read the feature file at `path`, and parse it with `@source` examples
+/// loaded relative to it. Returns the source and the parsed feature.
+pub fn read_feature(path: &Path) -> Result<(String, Result<Feature, ParseError>), Error> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let loader = FsExamplesLoader::for_feature(path);
+    let parsed = ParserOptions::strict()
+        .examples_loader(&loader)
+        .parse(&source);
+
+    Ok((source, parsed))
+}
@@ -0,0 +1,104 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Error};
+use clap::{Parser, ValueEnum};
+use gherkin::docs::{Docs, Status};
+
+mod common;
+use common::{collect_features, read_feature};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    Html,
+    Markdown,
+}
+
+/// Generate a documentation site from feature files.
+#[derive(Parser)]
+pub struct Cli {
+    #[clap(long, short, value_enum, default_value = "html")]
+    pub format: Format,
+
+    /// The directory to write the pages to.
+    #[clap(long, short, default_value = "docs")]
+    pub out: PathBuf,
+
+    /// The results of a run to show, with a `path:line status` line per
+    /// scenario, where the status is `passed`, `skipped` or `failed`.
+    #[clap(long, short)]
+    pub results: Option<PathBuf>,
+
+    /// Feature files, or directories to search for `.feature` files.
+    #[clap(required = true)]
+    pub paths: Vec<PathBuf>,
+}
+
+fn load_results(path: &Path, docs: &mut Docs) -> Result<(), Error> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    for (idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let invalid = || {
+            anyhow!(
+                "{}:{}: expected `path:line status`",
+                path.display(),
+                idx + 1
+            )
+        };
+        let (reference, status) = line.split_once(char::is_whitespace).ok_or_else(invalid)?;
+        let (file, scenario_line) = reference.rsplit_once(':').ok_or_else(invalid)?;
+        let scenario_line = scenario_line.parse().map_err(|_| invalid())?;
+        let status: Status = status
+            .trim()
+            .parse()
+            .map_err(|e| anyhow!("{}:{}: {e}", path.display(), idx + 1))?;
+
+        docs.add_result(file, scenario_line, status);
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<(), Error> {
+    let cli = Cli::parse();
+
+    let mut paths = Vec::new();
+    for path in &cli.paths {
+        collect_features(path, &mut paths)?;
+    }
+
+    let mut sources = Vec::new();
+    for path in &paths {
+        let (_, parsed) = read_feature(path)?;
+        let feature = parsed.with_context(|| format!("Failed to parse {}", path.display()))?;
+        sources.push((path.display().to_string(), feature));
+    }
+
+    let mut docs = Docs::default();
+    for (path, feature) in &sources {
+        docs.add(path, feature);
+    }
+    if let Some(results) = &cli.results {
+        load_results(results, &mut docs)?;
+    }
+
+    let pages = match cli.format {
+        Format::Html => docs.html(),
+        Format::Markdown => docs.markdown(),
+    };
+
+    std::fs::create_dir_all(&cli.out)
+        .with_context(|| format!("Failed to create {}", cli.out.display()))?;
+    for page in pages {
+        let path = cli.out.join(&page.file);
+        std::fs::write(&path, page.contents)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    Ok(())
+}
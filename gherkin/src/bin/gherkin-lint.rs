@@ -0,0 +1,94 @@
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use anyhow::{Context, Error};
+use clap::{Parser, ValueEnum};
+use gherkin::lint::{lint_parsed, Diagnostic, LintConfig};
+
+mod common;
+use common::{collect_features, read_feature};
+
+/// The configuration file used when `--config` is not given, if it exists.
+const DEFAULT_CONFIG: &str = "gherkin-lint.toml";
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    /// One `path:line:column: [rule] message` line per diagnostic.
+    Text,
+    /// A JSON array of diagnostics.
+    Json,
+}
+
+/// Check feature files against a set of lint rules.
+#[derive(Parser)]
+pub struct Cli {
+    /// The TOML file to read the rule configuration from.
+    #[clap(long, short)]
+    pub config: Option<PathBuf>,
+
+    #[clap(long, short, value_enum, default_value = "text")]
+    pub format: Format,
+
+    /// Feature files, or directories to search for `.feature` files.
+    #[clap(required = true)]
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(serde::Serialize)]
+struct FileDiagnostic<'a> {
+    file: &'a Path,
+    #[serde(flatten)]
+    diagnostic: &'a Diagnostic,
+}
+
+fn load_config(path: Option<&Path>) -> Result<LintConfig, Error> {
+    let path = match path {
+        Some(path) => path,
+        None if Path::new(DEFAULT_CONFIG).exists() => Path::new(DEFAULT_CONFIG),
+        None => return Ok(LintConfig::default()),
+    };
+
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    LintConfig::from_toml(&contents).with_context(|| format!("Invalid config {}", path.display()))
+}
+
+fn main() -> Result<ExitCode, Error> {
+    let cli = Cli::parse();
+    let config = load_config(cli.config.as_deref())?;
+
+    let mut features = Vec::new();
+    for path in &cli.paths {
+        collect_features(path, &mut features)?;
+    }
+
+    let mut diagnostics = Vec::new();
+    for path in &features {
+        let (source, parsed) = read_feature(path)?;
+        let file_diagnostics = lint_parsed(&source, parsed.as_ref(), &config);
+        diagnostics.extend(file_diagnostics.into_iter().map(|d| (path, d)));
+    }
+
+    match cli.format {
+        Format::Text => {
+            for (path, diagnostic) in &diagnostics {
+                println!("{}:{diagnostic}", path.display());
+            }
+        }
+        Format::Json => {
+            let diagnostics: Vec<_> = diagnostics
+                .iter()
+                .map(|(file, diagnostic)| FileDiagnostic { file, diagnostic })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&diagnostics)?);
+        }
+    }
+
+    Ok(if diagnostics.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
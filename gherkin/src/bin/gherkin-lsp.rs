@@ -0,0 +1,3 @@
+fn main() -> std::io::Result<()> {
+    gherkin::lsp::serve(std::io::stdin().lock(), std::io::stdout().lock())
+}
@@ -0,0 +1,129 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use anyhow::Error;
+use clap::{Parser, ValueEnum};
+use gherkin::stats::{ScenarioLength, Stats};
+
+mod common;
+use common::{collect_features, read_feature};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Format {
+    /// A human readable summary.
+    Text,
+    /// A JSON object, for dashboards.
+    Json,
+}
+
+/// Report statistics about a corpus of feature files.
+#[derive(Parser)]
+pub struct Cli {
+    #[clap(long, short, value_enum, default_value = "text")]
+    pub format: Format,
+
+    /// The amount of tags, step texts and scenarios to list.
+    #[clap(long, short, default_value = "10")]
+    pub top: usize,
+
+    /// Feature files, or directories to search for `.feature` files.
+    #[clap(required = true)]
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(serde::Serialize)]
+struct Count<'a> {
+    name: &'a str,
+    count: usize,
+}
+
+#[derive(serde::Serialize)]
+struct Report<'a> {
+    features: usize,
+    scenarios: usize,
+    scenario_outlines: usize,
+    outline_rows: usize,
+    steps: usize,
+    tags: Vec<Count<'a>>,
+    most_reused_steps: Vec<Count<'a>>,
+    longest_scenarios: Vec<&'a ScenarioLength>,
+}
+
+fn counts(counts: Vec<(&str, usize)>) -> Vec<Count<'_>> {
+    counts
+        .into_iter()
+        .map(|(name, count)| Count { name, count })
+        .collect()
+}
+
+fn print_counts(title: &str, counts: &[Count]) {
+    println!("\n{title}:");
+    for Count { name, count } in counts {
+        println!("{count:>6}  {name}");
+    }
+}
+
+fn main() -> Result<ExitCode, Error> {
+    let cli = Cli::parse();
+
+    let mut features = Vec::new();
+    for path in &cli.paths {
+        collect_features(path, &mut features)?;
+    }
+
+    // Files that cannot be parsed are reported and left out of the statistics.
+    let mut failed = false;
+    let mut stats = Stats::default();
+    for path in &features {
+        let feature = match read_feature(path)? {
+            (_, Ok(feature)) => feature,
+            (_, Err(e)) => {
+                eprintln!("{}:{}: {e}", path.display(), e.location.line);
+                failed = true;
+                continue;
+            }
+        };
+        stats.add(&path.display().to_string(), &feature);
+    }
+
+    let report = Report {
+        features: stats.features,
+        scenarios: stats.scenarios,
+        scenario_outlines: stats.scenario_outlines,
+        outline_rows: stats.outline_rows,
+        steps: stats.steps,
+        tags: counts(stats.most_used_tags(cli.top)),
+        most_reused_steps: counts(stats.most_reused_steps(cli.top)),
+        longest_scenarios: stats.longest_scenarios(cli.top),
+    };
+
+    match cli.format {
+        Format::Text => {
+            println!("Features:          {}", report.features);
+            println!("Scenarios:         {}", report.scenarios);
+            println!("Scenario outlines: {}", report.scenario_outlines);
+            println!("Outline rows:      {}", report.outline_rows);
+            println!("Steps:             {}", report.steps);
+
+            print_counts("Tags", &report.tags);
+            print_counts("Most reused steps", &report.most_reused_steps);
+
+            println!("\nLongest scenarios:");
+            for length in &report.longest_scenarios {
+                println!(
+                    "{:>6}  {}:{}  {}",
+                    length.steps,
+                    length.file,
+                    length.location.line,
+                    length.name.as_deref().unwrap_or_default()
+                );
+            }
+        }
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    Ok(if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
@@ -0,0 +1,673 @@
+//! Construction of features from code, without writing Gherkin text.
+//!
+//! [`Feature::builder`] returns a [`FeatureBuilder`], which adds
+//! backgrounds, scenarios and scenario outlines through closures:
+//!
+//! ```
+//! use gherkin::Feature;
+//!
+//! let feature = Feature::builder()
+//!     .name("Buying")
+//!     .tag("smoke")
+//!     .scenario("paying", |s| {
+//!         s.given("the following prices")
+//!             .table([["item", "price"], ["banana", "1"]])
+//!             .when("I buy a banana")
+//!             .then("I pay 1")
+//!     })
+//!     .scenario_outline("buying many", |o| {
+//!         o.given("<count> bananas")
+//!             .then("I pay <count>")
+//!             .examples(|e| e.row(["count"]).row(["1"]).row(["2"]))
+//!     })
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(feature.total_scenario_count(), 3);
+//! ```
+//!
+//! Tags, tables and examples are checked when the feature is built, and the
+//! first problem found is returned as a [`BuildError`].
+//!
+//! Built features have no source text, so all of their locations are
+//! [`Location::default()`]. Its line 0 is not a line of any file, and
+//! [`Feature::scenario_at_line`] finds nothing there.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use crate::{
+    lexer::find_placeholder, Background, DataTable, DataTableError, Feature, KeywordType, Location,
+    Scenario, ScenarioOutline, Step, StepData, StepType, TaggedScenarios,
+};
+
+/// An error produced when building a [`Feature`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// A tag is empty, starts with `@` or contains whitespace.
+    InvalidTag { tag: String },
+    /// A doc string or table was added before any step.
+    MissingStep,
+    /// The table of the step with the given text is invalid.
+    Table { step: String, error: DataTableError },
+    /// The scenario outline with the given name has no examples, or examples
+    /// without a header row.
+    MissingExamples { outline: String },
+    /// A row of the examples of the given scenario outline has a different
+    /// amount of values than the header.
+    ExamplesColumnCount {
+        outline: String,
+        expected: usize,
+        got: usize,
+    },
+    /// A step of the given scenario outline uses a placeholder that is not
+    /// a column of all of its examples.
+    UnknownPlaceholder {
+        outline: String,
+        placeholder: String,
+    },
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::InvalidTag { tag } => write!(
+                f,
+                "Invalid tag `{tag}`. Tags are given without `@`, and without whitespace"
+            ),
+            BuildError::MissingStep => write!(f, "Step data added before any step"),
+            BuildError::Table { step, error } => {
+                write!(f, "Invalid table of step `{step}`: {error}")
+            }
+            BuildError::MissingExamples { outline } => {
+                write!(f, "Scenario outline `{outline}` has no examples")
+            }
+            BuildError::ExamplesColumnCount {
+                outline,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Invalid column count in examples of `{outline}`. Expected {expected}, got {got}"
+            ),
+            BuildError::UnknownPlaceholder {
+                outline,
+                placeholder,
+            } => write!(
+                f,
+                "Scenario outline `{outline}` uses placeholder `<{placeholder}>`, which is not a \
+                 column of its examples"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for BuildError {}
+
+/// Adds methods for appending steps and their data to a builder with a
+/// `steps` list and an `error` slot.
+macro_rules! step_methods {
+    ($builder:ident) => {
+        impl $builder {
+            /// Append a step of type `ty`.
+            pub fn step(mut self, ty: StepType, text: impl Into<String>) -> Self {
+                self.steps.push(Step::new(ty, text.into(), None));
+                self
+            }
+
+            pub fn given(self, text: impl Into<String>) -> Self {
+                self.step(StepType::Given, text)
+            }
+
+            pub fn when(self, text: impl Into<String>) -> Self {
+                self.step(StepType::When, text)
+            }
+
+            pub fn then(self, text: impl Into<String>) -> Self {
+                self.step(StepType::Then, text)
+            }
+
+            pub fn and(self, text: impl Into<String>) -> Self {
+                self.step(StepType::And, text)
+            }
+
+            pub fn but(self, text: impl Into<String>) -> Self {
+                self.step(StepType::But, text)
+            }
+
+            /// Attach a doc string to the last step.
+            pub fn doc_string(mut self, text: impl Into<String>) -> Self {
+                self.set_data(StepData::DocString(text.into()));
+                self
+            }
+
+            /// Attach a table to the last step. The first row is the header.
+            pub fn table<R, C>(mut self, rows: R) -> Self
+            where
+                R: IntoIterator<Item = C>,
+                C: IntoIterator,
+                C::Item: Into<String>,
+            {
+                let step = self.steps.last().map(|s| s.description.clone());
+                match build_table(rows) {
+                    Ok(table) => self.set_data(StepData::DataTable(table)),
+                    Err(error) => self.fail(BuildError::Table {
+                        step: step.unwrap_or_default(),
+                        error,
+                    }),
+                }
+                self
+            }
+
+            fn set_data(&mut self, data: StepData) {
+                match self.steps.last_mut() {
+                    Some(step) => step.data = Some(data),
+                    None => self.fail(BuildError::MissingStep),
+                }
+            }
+
+            fn fail(&mut self, error: BuildError) {
+                self.error.get_or_insert(error);
+            }
+        }
+    };
+}
+
+fn build_table<R, C>(rows: R) -> Result<DataTable, DataTableError>
+where
+    R: IntoIterator<Item = C>,
+    C: IntoIterator,
+    C::Item: Into<String>,
+{
+    let mut rows = rows.into_iter().map(|r| to_strings(r));
+    let mut table = DataTable::new(rows.next().unwrap_or_default());
+    rows.try_for_each(|row| table.add_row(row))?;
+    Ok(table)
+}
+
+/// Check that `tag` can be written after an `@` in a feature file.
+fn check_tag(tag: String) -> Result<String, BuildError> {
+    if tag.is_empty() || tag.starts_with('@') || tag.contains(char::is_whitespace) {
+        return Err(BuildError::InvalidTag { tag });
+    }
+    Ok(tag)
+}
+
+fn to_strings(cells: impl IntoIterator<Item = impl Into<String>>) -> Vec<String> {
+    cells.into_iter().map(Into::into).collect()
+}
+
+/// Resolve the keyword type of `And`, `But` and `*` steps as the parser
+/// does, starting from `keyword_type`. Returns the type of the last step.
+fn resolve_keyword_types(steps: &mut [Step], mut keyword_type: KeywordType) -> KeywordType {
+    for step in steps {
+        step.keyword_type = KeywordType::of(step.ty);
+        if step.keyword_type == KeywordType::Unknown {
+            step.keyword_type = keyword_type;
+        }
+        keyword_type = step.keyword_type;
+    }
+    keyword_type
+}
+
+/// Builds a [`Feature`]. Created by [`Feature::builder`].
+#[derive(Debug, Clone, Default)]
+pub struct FeatureBuilder {
+    tags: Vec<String>,
+    name: Option<String>,
+    description: Option<String>,
+    background: Option<Background>,
+    scenarios: Vec<Scenario>,
+    scenario_outlines: Vec<ScenarioOutline>,
+    error: Option<BuildError>,
+}
+
+impl FeatureBuilder {
+    /// Add a tag, without the leading `@`.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        match check_tag(tag.into()) {
+            Ok(tag) => self.tags.push(tag),
+            Err(error) => self.merge_error(Some(error)),
+        }
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the background, replacing any previous one.
+    pub fn background(
+        mut self,
+        build: impl FnOnce(BackgroundBuilder) -> BackgroundBuilder,
+    ) -> Self {
+        let builder = build(BackgroundBuilder::default());
+        self.merge_error(builder.error);
+        self.background = Some(Background {
+            name: builder.name,
+            description: builder.description,
+            steps: builder.steps,
+            location: Location::default(),
+        });
+        self
+    }
+
+    pub fn scenario(
+        mut self,
+        name: impl Into<String>,
+        build: impl FnOnce(ScenarioBuilder) -> ScenarioBuilder,
+    ) -> Self {
+        let builder = build(ScenarioBuilder::default());
+        self.merge_error(builder.error);
+        self.scenarios.push(Scenario {
+            tags: builder.tags,
+            name: Some(name.into()),
+            description: builder.description,
+            steps: builder.steps,
+            location: Location::default(),
+        });
+        self
+    }
+
+    pub fn scenario_outline(
+        mut self,
+        name: impl Into<String>,
+        build: impl FnOnce(ScenarioOutlineBuilder) -> ScenarioOutlineBuilder,
+    ) -> Self {
+        let name = name.into();
+        let builder = build(ScenarioOutlineBuilder::default());
+        let error = builder.error.clone().or_else(|| builder.check(&name));
+        self.merge_error(error);
+
+        let scenarios = builder
+            .examples
+            .into_iter()
+            .filter_map(|mut e| {
+                let placeholders = (!e.rows.is_empty()).then(|| e.rows.remove(0))?;
+                let mut examples = TaggedScenarios::new(e.tags, placeholders, e.rows).ok()?;
+                examples.name = e.name;
+                examples.description = e.description;
+                Some(examples)
+            })
+            .collect();
+
+        self.scenario_outlines.push(ScenarioOutline {
+            tags: builder.tags,
+            name: Some(name),
+            description: builder.description,
+            steps: builder.steps,
+            scenarios,
+            location: Location::default(),
+        });
+        self
+    }
+
+    /// Build the feature, or return the first problem found while adding
+    /// to it.
+    pub fn build(self) -> Result<Feature, BuildError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let mut background = self.background;
+        let keyword_type = match &mut background {
+            Some(b) => resolve_keyword_types(&mut b.steps, KeywordType::Unknown),
+            None => KeywordType::Unknown,
+        };
+
+        let mut scenarios = self.scenarios;
+        for scenario in &mut scenarios {
+            resolve_keyword_types(&mut scenario.steps, keyword_type);
+        }
+
+        let mut scenario_outlines = self.scenario_outlines;
+        for outline in &mut scenario_outlines {
+            resolve_keyword_types(&mut outline.steps, keyword_type);
+        }
+
+        Ok(Feature {
+            tags: self.tags,
+            name: self.name,
+            description: self.description,
+            background,
+            scenarios,
+            scenario_outlines,
+            location: Location::default(),
+        })
+    }
+
+    fn merge_error(&mut self, error: Option<BuildError>) {
+        if self.error.is_none() {
+            self.error = error;
+        }
+    }
+}
+
+/// Builds the [`Background`] of a feature. See
+/// [`FeatureBuilder::background`].
+#[derive(Debug, Clone, Default)]
+pub struct BackgroundBuilder {
+    name: Option<String>,
+    description: Option<String>,
+    steps: Vec<Step>,
+    error: Option<BuildError>,
+}
+
+impl BackgroundBuilder {
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+step_methods!(BackgroundBuilder);
+
+/// Builds a [`Scenario`]. See [`FeatureBuilder::scenario`].
+#[derive(Debug, Clone, Default)]
+pub struct ScenarioBuilder {
+    tags: Vec<String>,
+    description: Option<String>,
+    steps: Vec<Step>,
+    error: Option<BuildError>,
+}
+
+impl ScenarioBuilder {
+    /// Add a tag, without the leading `@`.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        match check_tag(tag.into()) {
+            Ok(tag) => self.tags.push(tag),
+            Err(error) => self.fail(error),
+        }
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+step_methods!(ScenarioBuilder);
+
+/// Builds a [`ScenarioOutline`]. See [`FeatureBuilder::scenario_outline`].
+#[derive(Debug, Clone, Default)]
+pub struct ScenarioOutlineBuilder {
+    tags: Vec<String>,
+    description: Option<String>,
+    steps: Vec<Step>,
+    examples: Vec<ExamplesBuilder>,
+    error: Option<BuildError>,
+}
+
+impl ScenarioOutlineBuilder {
+    /// Add a tag, without the leading `@`.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        match check_tag(tag.into()) {
+            Ok(tag) => self.tags.push(tag),
+            Err(error) => self.fail(error),
+        }
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a set of examples.
+    pub fn examples(mut self, build: impl FnOnce(ExamplesBuilder) -> ExamplesBuilder) -> Self {
+        let mut examples = build(ExamplesBuilder::default());
+        if let Some(error) = examples.error.take() {
+            self.fail(error);
+        }
+        self.examples.push(examples);
+        self
+    }
+
+    /// Check the examples against each other and against the placeholders
+    /// used in the steps.
+    fn check(&self, outline: &str) -> Option<BuildError> {
+        if self.examples.is_empty() {
+            return Some(BuildError::MissingExamples {
+                outline: outline.to_string(),
+            });
+        }
+
+        let mut placeholders = Vec::new();
+        for step in &self.steps {
+            collect_placeholders(&step.description, &mut placeholders);
+            match &step.data {
+                Some(StepData::DocString(text)) => collect_placeholders(text, &mut placeholders),
+                Some(StepData::DataTable(table)) => table
+                    .header()
+                    .iter()
+                    .chain(table.rows().iter().flatten())
+                    .for_each(|cell| collect_placeholders(cell, &mut placeholders)),
+                None => {}
+            }
+        }
+
+        self.examples.iter().find_map(|examples| {
+            let Some(header) = examples.rows.first() else {
+                return Some(BuildError::MissingExamples {
+                    outline: outline.to_string(),
+                });
+            };
+
+            if let Some(row) = examples.rows.iter().find(|r| r.len() != header.len()) {
+                return Some(BuildError::ExamplesColumnCount {
+                    outline: outline.to_string(),
+                    expected: header.len(),
+                    got: row.len(),
+                });
+            }
+
+            placeholders
+                .iter()
+                .find(|p| !header.iter().any(|h| h == *p))
+                .map(|p| BuildError::UnknownPlaceholder {
+                    outline: outline.to_string(),
+                    placeholder: p.to_string(),
+                })
+        })
+    }
+}
+
+step_methods!(ScenarioOutlineBuilder);
+
+fn collect_placeholders<'a>(mut text: &'a str, placeholders: &mut Vec<&'a str>) {
+    while let Some(range) = find_placeholder(text) {
+        placeholders.push(&text[range.start + 1..range.end - 1]);
+        text = &text[range.end..];
+    }
+}
+
+/// Builds the examples of a scenario outline. See
+/// [`ScenarioOutlineBuilder::examples`].
+#[derive(Debug, Clone, Default)]
+pub struct ExamplesBuilder {
+    tags: Vec<String>,
+    name: Option<String>,
+    description: Option<String>,
+    rows: Vec<Vec<String>>,
+    error: Option<BuildError>,
+}
+
+impl ExamplesBuilder {
+    /// Add a tag, without the leading `@`.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        match check_tag(tag.into()) {
+            Ok(tag) => self.tags.push(tag),
+            Err(error) => self.fail(error),
+        }
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Add a row. The first row is the header, naming the placeholders.
+    pub fn row(mut self, row: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.rows.push(to_strings(row));
+        self
+    }
+
+    fn fail(&mut self, error: BuildError) {
+        self.error.get_or_insert(error);
+    }
+}
+
+#[test]
+fn build() {
+    let feature = Feature::builder()
+        .tag("shop")
+        .name("Buying")
+        .description("Customers buy things.")
+        .background(|b| b.name("a shop").given("a shop").and("a customer"))
+        .scenario("paying", |s| {
+            s.and("the following prices")
+                .table([["item", "price"], ["banana", "1"]])
+                .then("the receipt reads")
+                .doc_string("1 banana")
+        })
+        .scenario_outline("buying many", |o| {
+            o.tag("slow")
+                .given("<count> items")
+                .then("I pay <count>")
+                .examples(|e| {
+                    e.tag("small")
+                        .name("small amounts")
+                        .row(["count"])
+                        .row(["1"])
+                })
+        })
+        .build()
+        .unwrap();
+
+    let expected = crate::Parser::parse_feature(
+        r#"@shop
+Feature: Buying
+  Customers buy things.
+
+  Background: a shop
+    Given a shop
+    And a customer
+
+  Scenario: paying
+    And the following prices
+      | item   | price |
+      | banana | 1     |
+    Then the receipt reads
+      """
+      1 banana
+      """
+
+  @slow
+  Scenario Outline: buying many
+    Given <count> items
+    Then I pay <count>
+
+    @small
+    Examples: small amounts
+      | count |
+      | 1     |
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(feature.to_string(), expected.to_string());
+    assert_eq!(
+        feature.scenarios[0].steps[0].keyword_type,
+        KeywordType::Context
+    );
+    assert_eq!(feature.total_scenario_count(), 2);
+    assert_eq!(feature.scenario_at_line(0), None);
+    assert_eq!(feature.scenario_at_line(1), None);
+}
+
+#[test]
+fn build_errors() {
+    let error = |feature: FeatureBuilder| feature.build().unwrap_err();
+
+    assert_eq!(
+        error(Feature::builder().scenario("x", |s| s.doc_string("text"))),
+        BuildError::MissingStep
+    );
+    for tag in ["", "@fast", "very slow"] {
+        let invalid = BuildError::InvalidTag {
+            tag: tag.to_string(),
+        };
+        assert_eq!(error(Feature::builder().tag(tag)), invalid);
+        assert_eq!(
+            error(Feature::builder().scenario("x", |s| s.tag(tag).given("a"))),
+            invalid
+        );
+        assert_eq!(
+            error(Feature::builder().scenario_outline("x", |o| {
+                o.given("<a>")
+                    .examples(|e| e.tag(tag).row(["a"]).row(["1"]))
+            })),
+            invalid
+        );
+    }
+    assert_eq!(
+        error(
+            Feature::builder().scenario("x", |s| s.given("a").table([vec!["a", "b"], vec!["1"]]))
+        ),
+        BuildError::Table {
+            step: "a".to_string(),
+            error: DataTableError::ColumnCountMismatch {
+                expected: 2,
+                got: 1
+            }
+        }
+    );
+    assert_eq!(
+        error(Feature::builder().scenario_outline("x", |o| o.given("<a>"))),
+        BuildError::MissingExamples {
+            outline: "x".to_string()
+        }
+    );
+    assert_eq!(
+        error(Feature::builder().scenario_outline("x", |o| {
+            o.given("<a>").examples(|e| e.row(["a", "b"]).row(["1"]))
+        })),
+        BuildError::ExamplesColumnCount {
+            outline: "x".to_string(),
+            expected: 2,
+            got: 1
+        }
+    );
+    assert_eq!(
+        error(Feature::builder().scenario_outline("x", |o| {
+            o.given("<a>")
+                .doc_string("<b>")
+                .examples(|e| e.row(["a"]).row(["1"]))
+        })),
+        BuildError::UnknownPlaceholder {
+            outline: "x".to_string(),
+            placeholder: "b".to_string()
+        }
+    );
+}
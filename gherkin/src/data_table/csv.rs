@@ -0,0 +1,178 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::{DataTable, TableFormatError};
+
+impl DataTable {
+    /// Read a table from CSV as described in RFC 4180. The first record is
+    /// used as the header.
+    ///
+    /// Both `\r\n` and `\n` are accepted as record separators.
+    pub fn from_csv(input: &str) -> Result<DataTable, TableFormatError> {
+        let mut records = parse_records(input)?.into_iter();
+
+        let Some((_, header)) = records.next() else {
+            return Err(TableFormatError::new(1, "Expected a header record"));
+        };
+
+        let mut table = DataTable::new(header);
+        for (line, record) in records {
+            table
+                .add_row(record)
+                .map_err(|e| TableFormatError::new(line, e.to_string()))?;
+        }
+
+        Ok(table)
+    }
+
+    /// Write this table as CSV as described in RFC 4180, using `\r\n` as
+    /// record separator. Fields are only quoted when necessary.
+    pub fn to_csv(&self) -> String {
+        let mut output = String::new();
+
+        for row in core::iter::once(&self.header).chain(self.rows.iter()) {
+            for (idx, field) in row.iter().enumerate() {
+                if idx != 0 {
+                    output.push(',');
+                }
+
+                if field.contains([',', '"', '\r', '\n']) {
+                    output.push('"');
+                    output.push_str(&field.replace('"', "\"\""));
+                    output.push('"');
+                } else {
+                    output.push_str(field);
+                }
+            }
+            output.push_str("\r\n");
+        }
+
+        output
+    }
+}
+
+/// Split `input` into records, each paired with the line it starts on.
+fn parse_records(input: &str) -> Result<Vec<(usize, Vec<String>)>, TableFormatError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut record_line = 1;
+
+    let mut chars = input.chars().peekable();
+
+    // Whether anything has been read for the current record, so that a
+    // trailing line break does not produce an empty record.
+    let mut in_record = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if field.is_empty() => {
+                let quote_line = line;
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                            field.push('"');
+                        }
+                        Some('"') => break,
+                        Some(c) => {
+                            if c == '\n' {
+                                line += 1;
+                            }
+                            field.push(c);
+                        }
+                        None => {
+                            return Err(TableFormatError::new(
+                                quote_line,
+                                "Unterminated quoted field",
+                            ))
+                        }
+                    }
+                }
+
+                if !matches!(chars.peek(), None | Some(',' | '\r' | '\n')) {
+                    return Err(TableFormatError::new(
+                        line,
+                        "Unexpected character after closing quote",
+                    ));
+                }
+            }
+            ',' => record.push(core::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                record.push(core::mem::take(&mut field));
+                records.push((record_line, core::mem::take(&mut record)));
+                line += 1;
+                record_line = line;
+                in_record = false;
+                continue;
+            }
+            c => field.push(c),
+        }
+        in_record = true;
+    }
+
+    if in_record {
+        record.push(field);
+        records.push((record_line, record));
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{super::make_table, DataTable, TableFormatError};
+
+    #[test]
+    fn round_trip() {
+        let table = make_table(
+            &["name", "quote"],
+            &[
+                &["alice", "hello, world"],
+                &["bob", "say \"hi\""],
+                &["carol", "two\nlines"],
+                &["", "empty"],
+            ],
+        );
+
+        let csv = table.to_csv();
+        assert_eq!(
+            csv,
+            "name,quote\r\nalice,\"hello, world\"\r\nbob,\"say \"\"hi\"\"\"\r\n\
+             carol,\"two\nlines\"\r\n,empty\r\n"
+        );
+        assert_eq!(DataTable::from_csv(&csv), Ok(table));
+    }
+
+    #[test]
+    fn line_feeds() {
+        let table = DataTable::from_csv("a,b\n1,2\n3,4").unwrap();
+        assert_eq!(table, make_table(&["a", "b"], &[&["1", "2"], &["3", "4"]]));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            DataTable::from_csv("a,b\n\"1,2\n"),
+            Err(TableFormatError::new(2, "Unterminated quoted field"))
+        );
+        assert_eq!(
+            DataTable::from_csv("a,b\n\"1\"x,2\n"),
+            Err(TableFormatError::new(
+                2,
+                "Unexpected character after closing quote"
+            ))
+        );
+        assert_eq!(
+            DataTable::from_csv("a,b\n\"1\n2\",2\n3\n")
+                .unwrap_err()
+                .line,
+            4
+        );
+        assert!(DataTable::from_csv("").is_err());
+    }
+}
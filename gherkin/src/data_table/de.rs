@@ -0,0 +1,447 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use core::fmt;
+
+use serde::de::{
+    self, value::StrDeserializer, DeserializeSeed, Deserializer, IntoDeserializer, MapAccess,
+    SeqAccess, Visitor,
+};
+
+use super::DataTable;
+
+/// An error produced while deserializing a [`DataTable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeserializeError {
+    /// The index of the row (excluding the header) that failed.
+    pub row: Option<usize>,
+    /// The name of the column that failed.
+    pub column: Option<String>,
+    pub message: String,
+}
+
+impl DeserializeError {
+    fn with_row(mut self, row: usize) -> Self {
+        self.row.get_or_insert(row);
+        self
+    }
+
+    fn with_column(mut self, column: &str) -> Self {
+        self.column.get_or_insert_with(|| column.to_string());
+        self
+    }
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.row, &self.column) {
+            (Some(row), Some(column)) => write!(f, "Row {row}, column `{column}`: ")?,
+            (Some(row), None) => write!(f, "Row {row}: ")?,
+            (None, Some(column)) => write!(f, "Column `{column}`: ")?,
+            (None, None) => {}
+        }
+        f.write_str(&self.message)
+    }
+}
+
+impl core::error::Error for DeserializeError {}
+
+impl de::Error for DeserializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self {
+            row: None,
+            column: None,
+            message: msg.to_string(),
+        }
+    }
+}
+
+impl DataTable {
+    /// Deserialize the rows of this table into `T`.
+    ///
+    /// The table is presented as a sequence of rows, where every row is a
+    /// map from header name to cell value. Cell values are converted from
+    /// strings into the requested type, and empty cells deserialize to
+    /// `None` for optional fields.
+    ///
+    /// ```
+    /// # use gherkin::DataTable;
+    /// #[derive(serde::Deserialize)]
+    /// struct User {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let table = DataTable::new_populated(
+    ///     vec!["name".into(), "age".into()],
+    ///     vec![vec!["alice".into(), "30".into()]],
+    /// )
+    /// .unwrap();
+    ///
+    /// let users: Vec<User> = table.deserialize().unwrap();
+    /// assert_eq!(users[0].age, 30);
+    /// ```
+    pub fn deserialize<'de, T>(&'de self) -> Result<T, DeserializeError>
+    where
+        T: de::Deserialize<'de>,
+    {
+        T::deserialize(TableDeserializer { table: self })
+    }
+}
+
+struct TableDeserializer<'de> {
+    table: &'de DataTable,
+}
+
+impl<'de> Deserializer<'de> for TableDeserializer<'de> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(Rows {
+            table: self.table,
+            index: 0,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+struct Rows<'de> {
+    table: &'de DataTable,
+    index: usize,
+}
+
+impl<'de> SeqAccess<'de> for Rows<'de> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let Some(row) = self.table.rows.get(self.index) else {
+            return Ok(None);
+        };
+
+        let index = self.index;
+        self.index += 1;
+
+        seed.deserialize(RowDeserializer {
+            header: &self.table.header,
+            row,
+        })
+        .map(Some)
+        .map_err(|e| e.with_row(index))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.table.rows.len() - self.index)
+    }
+}
+
+struct RowDeserializer<'de> {
+    header: &'de [String],
+    row: &'de [String],
+}
+
+impl<'de> Deserializer<'de> for RowDeserializer<'de> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(Cells {
+            header: self.header,
+            row: self.row,
+            index: 0,
+        })
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(Cells {
+            header: self.header,
+            row: self.row,
+            index: 0,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+struct Cells<'de> {
+    header: &'de [String],
+    row: &'de [String],
+    index: usize,
+}
+
+impl<'de> Cells<'de> {
+    fn next_cell<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<T::Value, DeserializeError> {
+        let column = &self.header[self.index];
+        let value = &self.row[self.index];
+        self.index += 1;
+
+        seed.deserialize(CellDeserializer { value })
+            .map_err(|e| e.with_column(column))
+    }
+}
+
+impl<'de> MapAccess<'de> for Cells<'de> {
+    type Error = DeserializeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some(column) = self.header.get(self.index) else {
+            return Ok(None);
+        };
+
+        let key: StrDeserializer<'_, DeserializeError> = column.as_str().into_deserializer();
+        seed.deserialize(key).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.next_cell(seed)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.header.len() - self.index)
+    }
+}
+
+impl<'de> SeqAccess<'de> for Cells<'de> {
+    type Error = DeserializeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        if self.index < self.row.len() {
+            self.next_cell(seed).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.row.len() - self.index)
+    }
+}
+
+struct CellDeserializer<'de> {
+    value: &'de str,
+}
+
+impl CellDeserializer<'_> {
+    fn parse<T>(&self, expected: &str) -> Result<T, DeserializeError>
+    where
+        T: core::str::FromStr,
+        T::Err: fmt::Display,
+    {
+        self.value.parse().map_err(|e| {
+            de::Error::custom(format!("Invalid {expected} value `{}`: {e}", self.value))
+        })
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident: $ty:ty,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                visitor.$visit(self.parse::<$ty>(stringify!($ty))?)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for CellDeserializer<'de> {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_borrowed_str(self.value)
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool: bool,
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_i128 => visit_i128: i128,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_u128 => visit_u128: u128,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64,
+        deserialize_char => visit_char: char,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.value.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.value.is_empty() {
+            visitor.visit_unit()
+        } else {
+            Err(de::Error::invalid_type(
+                de::Unexpected::Str(self.value),
+                &visitor,
+            ))
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        let value: StrDeserializer<'_, DeserializeError> = self.value.into_deserializer();
+        visitor.visit_enum(value)
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf seq tuple tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::Deserialize;
+
+    use super::{super::make_table, DeserializeError};
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Role {
+        Admin,
+        User,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct User {
+        name: String,
+        age: u32,
+        #[serde(rename = "e-mail")]
+        email: Option<String>,
+        role: Role,
+        active: bool,
+    }
+
+    #[test]
+    fn structs() {
+        let table = make_table(
+            &["name", "age", "e-mail", "role", "active"],
+            &[
+                &["alice", "30", "alice@example.com", "admin", "true"],
+                &["bob", "25", "", "user", "false"],
+            ],
+        );
+
+        let users: Vec<User> = table.deserialize().unwrap();
+
+        assert_eq!(
+            users,
+            [
+                User {
+                    name: "alice".into(),
+                    age: 30,
+                    email: Some("alice@example.com".into()),
+                    role: Role::Admin,
+                    active: true,
+                },
+                User {
+                    name: "bob".into(),
+                    age: 25,
+                    email: None,
+                    role: Role::User,
+                    active: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn tuples_and_borrowed() {
+        let table = make_table(&["name", "score"], &[&["alice", "1.5"], &["bob", "-2"]]);
+
+        let rows: Vec<(&str, f64)> = table.deserialize().unwrap();
+        assert_eq!(rows, [("alice", 1.5), ("bob", -2.0)]);
+    }
+
+    #[test]
+    fn errors() {
+        let table = make_table(
+            &["name", "age", "e-mail", "role", "active"],
+            &[
+                &["alice", "30", "", "admin", "true"],
+                &["bob", "old", "", "user", "false"],
+            ],
+        );
+
+        let error = table.deserialize::<Vec<User>>().unwrap_err();
+        assert_eq!(error.row, Some(1));
+        assert_eq!(error.column.as_deref(), Some("age"));
+        assert!(error.to_string().starts_with("Row 1, column `age`: "));
+
+        let table = make_table(&["name"], &[&["alice"]]);
+        let error: DeserializeError = table.deserialize::<Vec<User>>().unwrap_err();
+        assert_eq!(error.row, Some(0));
+        assert_eq!(error.column, None);
+        assert_eq!(error.message, "missing field `age`");
+    }
+}
@@ -0,0 +1,304 @@
+use alloc::{string::String, vec, vec::Vec};
+
+use core::fmt;
+
+use super::{write_rows, DataTable};
+
+/// The difference of a single row between an expected and an actual table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowDiff {
+    /// The row is present, unchanged, in both tables.
+    Unchanged(Vec<String>),
+    /// The row is only present in the expected table.
+    Missing(Vec<String>),
+    /// The row is only present in the actual table.
+    Surplus(Vec<String>),
+    /// The row is present in both tables, but some of its cells differ.
+    Changed {
+        expected: Vec<String>,
+        actual: Vec<String>,
+        /// The indices of the cells that differ.
+        columns: Vec<usize>,
+    },
+}
+
+/// The result of comparing two [`DataTable`]s.
+///
+/// The header is compared like any other row and is always the first
+/// entry of [`TableDiff::rows`].
+///
+/// The [`Display`](fmt::Display) implementation renders the difference as
+/// a Gherkin table, where missing rows are marked with `-` and surplus rows
+/// with `+`. A changed row is rendered as the expected row followed by the
+/// actual row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableDiff {
+    rows: Vec<RowDiff>,
+}
+
+impl TableDiff {
+    pub fn rows(&self) -> &[RowDiff] {
+        &self.rows
+    }
+
+    /// Whether the compared tables differ.
+    pub fn has_changes(&self) -> bool {
+        self.rows
+            .iter()
+            .any(|r| !matches!(r, RowDiff::Unchanged(_)))
+    }
+
+    pub fn missing(&self) -> impl Iterator<Item = &Vec<String>> {
+        self.rows.iter().filter_map(|r| match r {
+            RowDiff::Missing(row) => Some(row),
+            _ => None,
+        })
+    }
+
+    pub fn surplus(&self) -> impl Iterator<Item = &Vec<String>> {
+        self.rows.iter().filter_map(|r| match r {
+            RowDiff::Surplus(row) => Some(row),
+            _ => None,
+        })
+    }
+
+    /// All changed rows, as `(expected, actual, changed columns)`.
+    pub fn changed(&self) -> impl Iterator<Item = (&Vec<String>, &Vec<String>, &Vec<usize>)> {
+        self.rows.iter().filter_map(|r| match r {
+            RowDiff::Changed {
+                expected,
+                actual,
+                columns,
+            } => Some((expected, actual, columns)),
+            _ => None,
+        })
+    }
+}
+
+impl fmt::Display for TableDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rows = Vec::new();
+        for row in &self.rows {
+            match row {
+                RowDiff::Unchanged(row) => rows.push(("", row.as_slice())),
+                RowDiff::Missing(row) => rows.push(("-", row.as_slice())),
+                RowDiff::Surplus(row) => rows.push(("+", row.as_slice())),
+                RowDiff::Changed {
+                    expected, actual, ..
+                } => {
+                    rows.push(("-", expected.as_slice()));
+                    rows.push(("+", actual.as_slice()));
+                }
+            }
+        }
+
+        write_rows(f, &rows)
+    }
+}
+
+fn changed_columns(expected: &[String], actual: &[String]) -> Vec<usize> {
+    (0..expected.len().max(actual.len()))
+        .filter(|&idx| expected.get(idx) != actual.get(idx))
+        .collect()
+}
+
+fn changed(expected: &[String], actual: &[String]) -> RowDiff {
+    RowDiff::Changed {
+        expected: expected.to_vec(),
+        actual: actual.to_vec(),
+        columns: changed_columns(expected, actual),
+    }
+}
+
+fn compare_headers(expected: &DataTable, actual: &DataTable) -> RowDiff {
+    if expected.header == actual.header {
+        RowDiff::Unchanged(expected.header.clone())
+    } else {
+        changed(&expected.header, &actual.header)
+    }
+}
+
+/// Push the rows between two matching rows. Rows are paired up as changed
+/// rows as long as both sides have rows left.
+fn push_unmatched(out: &mut Vec<RowDiff>, expected: &[Vec<String>], actual: &[Vec<String>]) {
+    let paired = expected.len().min(actual.len());
+
+    for (expected, actual) in expected.iter().zip(actual) {
+        out.push(changed(expected, actual));
+    }
+
+    out.extend(expected[paired..].iter().cloned().map(RowDiff::Missing));
+    out.extend(actual[paired..].iter().cloned().map(RowDiff::Surplus));
+}
+
+impl DataTable {
+    /// Compare this (expected) table against `actual`, taking the order of
+    /// rows into account.
+    pub fn diff(&self, actual: &DataTable) -> TableDiff {
+        let expected = &self.rows;
+        let actual_rows = &actual.rows;
+
+        // Longest common subsequence of the rows of both tables.
+        let (n, m) = (expected.len(), actual_rows.len());
+        let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if expected[i] == actual_rows[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let mut rows = vec![compare_headers(self, actual)];
+
+        let (mut i, mut j) = (0, 0);
+        let (mut unmatched_i, mut unmatched_j) = (0, 0);
+        while i < n && j < m {
+            if expected[i] == actual_rows[j] {
+                push_unmatched(
+                    &mut rows,
+                    &expected[unmatched_i..i],
+                    &actual_rows[unmatched_j..j],
+                );
+                rows.push(RowDiff::Unchanged(expected[i].clone()));
+                i += 1;
+                j += 1;
+                unmatched_i = i;
+                unmatched_j = j;
+            } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        push_unmatched(
+            &mut rows,
+            &expected[unmatched_i..],
+            &actual_rows[unmatched_j..],
+        );
+
+        TableDiff { rows }
+    }
+
+    /// Compare this (expected) table against `actual`, ignoring the order
+    /// of rows.
+    ///
+    /// Rows that are only present in one of the tables are reported as
+    /// missing or surplus rows, never as changed rows.
+    pub fn diff_unordered(&self, actual: &DataTable) -> TableDiff {
+        let mut rows = vec![compare_headers(self, actual)];
+
+        let mut unmatched: Vec<Option<&Vec<String>>> = actual.rows.iter().map(Some).collect();
+
+        for row in &self.rows {
+            let matching = unmatched.iter_mut().find(|r| *r == &Some(row));
+
+            if let Some(matching) = matching {
+                *matching = None;
+                rows.push(RowDiff::Unchanged(row.clone()));
+            } else {
+                rows.push(RowDiff::Missing(row.clone()));
+            }
+        }
+
+        rows.extend(
+            unmatched
+                .into_iter()
+                .flatten()
+                .cloned()
+                .map(RowDiff::Surplus),
+        );
+
+        TableDiff { rows }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{super::make_table, RowDiff};
+
+    fn row(cells: &[&str]) -> Vec<String> {
+        cells.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn equal() {
+        let table = make_table(&["name", "age"], &[&["alice", "30"], &["bob", "25"]]);
+        let diff = table.diff(&table.clone());
+
+        assert!(!diff.has_changes());
+        assert!(!table.diff_unordered(&table).has_changes());
+        assert_eq!(diff.to_string(), table.to_string());
+    }
+
+    #[test]
+    fn ordered() {
+        let expected = make_table(
+            &["name", "age"],
+            &[&["alice", "30"], &["bob", "25"], &["carol", "41"]],
+        );
+        let actual = make_table(
+            &["name", "age"],
+            &[&["alice", "31"], &["carol", "41"], &["dave", "12"]],
+        );
+
+        let diff = expected.diff(&actual);
+
+        assert!(diff.has_changes());
+        assert_eq!(
+            diff.rows(),
+            [
+                RowDiff::Unchanged(row(&["name", "age"])),
+                RowDiff::Changed {
+                    expected: row(&["alice", "30"]),
+                    actual: row(&["alice", "31"]),
+                    columns: vec![1],
+                },
+                RowDiff::Missing(row(&["bob", "25"])),
+                RowDiff::Unchanged(row(&["carol", "41"])),
+                RowDiff::Surplus(row(&["dave", "12"])),
+            ]
+        );
+
+        let expected_text = "  | name  | age |
+- | alice | 30  |
++ | alice | 31  |
+- | bob   | 25  |
+  | carol | 41  |
++ | dave  | 12  |";
+
+        assert_eq!(diff.to_string(), expected_text);
+    }
+
+    #[test]
+    fn unordered() {
+        let expected = make_table(
+            &["name", "age"],
+            &[&["alice", "30"], &["bob", "25"], &["bob", "25"]],
+        );
+        let actual = make_table(
+            &["name", "age"],
+            &[&["bob", "25"], &["alice", "30"], &["carol", "41"]],
+        );
+
+        let diff = expected.diff_unordered(&actual);
+
+        assert_eq!(diff.missing().collect::<Vec<_>>(), [&row(&["bob", "25"])]);
+        assert_eq!(diff.surplus().collect::<Vec<_>>(), [&row(&["carol", "41"])]);
+        assert_eq!(diff.changed().count(), 0);
+    }
+
+    #[test]
+    fn header() {
+        let expected = make_table(&["name", "age"], &[]);
+        let actual = make_table(&["name", "height"], &[]);
+
+        let diff = expected.diff(&actual);
+        let changed: Vec<_> = diff.changed().collect();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].2, &vec![1]);
+    }
+}
@@ -0,0 +1,252 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::{DataTable, TableFormatError};
+
+impl DataTable {
+    /// Read a table in the (GitHub flavored) Markdown table syntax.
+    ///
+    /// The first row is used as the header and must be followed by a
+    /// delimiter row. Blank lines before and after the table are ignored.
+    pub fn from_markdown(input: &str) -> Result<DataTable, TableFormatError> {
+        let mut lines = input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim()))
+            .skip_while(|(_, line)| line.is_empty())
+            .take_while(|(_, line)| !line.is_empty());
+
+        let Some((_, header)) = lines.next() else {
+            return Err(TableFormatError::new(1, "Expected a header row"));
+        };
+        let header = split_row(header);
+
+        match lines.next() {
+            Some((line_no, line)) if is_delimiter_row(line) => {
+                let columns = split_row(line).len();
+                if columns != header.len() {
+                    return Err(TableFormatError::new(
+                        line_no,
+                        format!(
+                            "Delimiter row has {columns} columns, header has {}",
+                            header.len()
+                        ),
+                    ));
+                }
+            }
+            Some((line_no, _)) => {
+                return Err(TableFormatError::new(line_no, "Expected a delimiter row"))
+            }
+            None => return Err(TableFormatError::new(2, "Expected a delimiter row")),
+        }
+
+        let mut table = DataTable::new(header);
+        for (line_no, line) in lines {
+            table
+                .add_row(split_row(line))
+                .map_err(|e| TableFormatError::new(line_no, e.to_string()))?;
+        }
+
+        Ok(table)
+    }
+
+    /// Write this table as an aligned Markdown table.
+    pub fn to_markdown(&self) -> String {
+        let escaped: Vec<Vec<String>> = core::iter::once(&self.header)
+            .chain(self.rows.iter())
+            .map(|row| row.iter().map(|c| escape_cell(c)).collect())
+            .collect();
+
+        let widths: Vec<usize> = (0..self.header.len())
+            .map(|col| {
+                escaped
+                    .iter()
+                    .map(|row| row[col].chars().count())
+                    .max()
+                    .unwrap_or(0)
+                    .max(3)
+            })
+            .collect();
+
+        let mut output = String::new();
+        let mut push_row = |row: &mut dyn Iterator<Item = String>| {
+            output.push('|');
+            for cell in row {
+                output.push(' ');
+                output.push_str(&cell);
+                output.push_str(" |");
+            }
+            output.push('\n');
+        };
+
+        let mut rows = escaped.iter();
+        if let Some(header) = rows.next() {
+            push_row(&mut header.iter().zip(&widths).map(|(c, w)| format!("{c:w$}")));
+            push_row(&mut widths.iter().map(|w| "-".repeat(*w)));
+        }
+
+        for row in rows {
+            push_row(&mut row.iter().zip(&widths).map(|(c, w)| format!("{c:w$}")));
+        }
+
+        output
+    }
+}
+
+/// Escape `\`, `|` and literal `<br>`s, and encode newlines as `<br>`.
+fn escape_cell(cell: &str) -> String {
+    cell.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("<br>", "\\<br>")
+        .replace('\n', "<br>")
+}
+
+/// Undo [`escape_cell`]. Other backslashes are kept as they are.
+fn unescape_cell(cell: &str) -> String {
+    let mut unescaped = String::new();
+    let mut rest = cell;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(escaped) = rest.strip_prefix('\\') {
+            if let Some(c @ ('\\' | '|' | '<')) = escaped.chars().next() {
+                unescaped.push(c);
+                rest = &escaped[1..];
+                continue;
+            }
+        } else if let Some(after) = rest.strip_prefix("<br>") {
+            unescaped.push('\n');
+            rest = after;
+            continue;
+        }
+
+        unescaped.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    unescaped
+}
+
+fn is_delimiter_row(line: &str) -> bool {
+    let cells = split_row(line);
+    !cells.is_empty()
+        && cells.iter().all(|c| {
+            let c = c.strip_prefix(':').unwrap_or(c);
+            let c = c.strip_suffix(':').unwrap_or(c);
+            !c.is_empty() && c.chars().all(|c| c == '-')
+        })
+}
+
+/// Split a row into its cells. Leading and trailing pipes are optional, and
+/// cells are unescaped as written by [`escape_cell`].
+fn split_row(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = match line.strip_suffix('|') {
+        // A pipe after an odd amount of backslashes is escaped.
+        Some(rest) if (rest.len() - rest.trim_end_matches('\\').len()) % 2 == 0 => rest,
+        _ => line,
+    };
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                cell.push(c);
+                cell.extend(chars.next());
+            }
+            '|' => cells.push(core::mem::take(&mut cell)),
+            c => cell.push(c),
+        }
+    }
+
+    cells.push(cell);
+
+    cells.iter().map(|c| unescape_cell(c.trim())).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{super::make_table, DataTable};
+
+    #[test]
+    fn round_trip() {
+        let table = make_table(
+            &["name", "comment"],
+            &[&["alice", "a | b"], &["bob", "two\nlines"], &["carol", ""]],
+        );
+
+        let markdown = table.to_markdown();
+        assert_eq!(
+            markdown,
+            "| name  | comment      |
+| ----- | ------------ |
+| alice | a \\| b       |
+| bob   | two<br>lines |
+| carol |              |
+"
+        );
+        assert_eq!(DataTable::from_markdown(&markdown), Ok(table));
+    }
+
+    #[test]
+    fn round_trip_escapes() {
+        let table = make_table(
+            &["cell"],
+            &[
+                &["C:\\path\\"],
+                &["\\|"],
+                &["a<br>b"],
+                &["a\\<br>b"],
+                &["<br>\n<br>"],
+            ],
+        );
+
+        let markdown = table.to_markdown();
+        assert!(markdown.contains("| C:\\\\path\\\\ "), "{markdown}");
+        assert!(markdown.contains("| a\\<br>b "), "{markdown}");
+        assert_eq!(DataTable::from_markdown(&markdown), Ok(table));
+    }
+
+    #[test]
+    fn without_outer_pipes() {
+        let table = DataTable::from_markdown(
+            "
+a | b
+:-- | --:
+1 | 2
+",
+        )
+        .unwrap();
+
+        assert_eq!(table, make_table(&["a", "b"], &[&["1", "2"]]));
+    }
+
+    #[test]
+    fn empty_last_cell() {
+        let table = DataTable::from_markdown("| a | b |\n|---|---|\n| 1 | |\n| 2 |   |").unwrap();
+        assert_eq!(table, make_table(&["a", "b"], &[&["1", ""], &["2", ""]]));
+
+        let table = DataTable::from_markdown("a | b\n--|--\n1 | \\|").unwrap();
+        assert_eq!(table, make_table(&["a", "b"], &[&["1", "|"]]));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            DataTable::from_markdown("| a |\n| b |").unwrap_err().line,
+            2
+        );
+        assert_eq!(
+            DataTable::from_markdown("| a | b |\n|---|---|\n| 1 |")
+                .unwrap_err()
+                .line,
+            3
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use alloc::{
+    borrow::Cow,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+mod diff;
+pub use diff::{RowDiff, TableDiff};
+
+mod csv;
+mod markdown;
+
+#[cfg(feature = "serde")]
+mod de;
+#[cfg(feature = "serde")]
+pub use de::DeserializeError;
+
+/// An error produced when accessing or modifying a [`DataTable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataTableError {
+    /// A row did not have the same amount of cells as the header.
+    ColumnCountMismatch { expected: usize, got: usize },
+    /// No column with the given name exists in the header.
+    UnknownColumn(String),
+    /// The requested row does not exist.
+    RowOutOfBounds { row: usize, len: usize },
+    /// The requested column does not exist.
+    ColumnOutOfBounds { column: usize, len: usize },
+    /// The operation requires a table with exactly two columns.
+    NotTwoColumns(usize),
+}
+
+impl fmt::Display for DataTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataTableError::ColumnCountMismatch { expected, got } => write!(
+                f,
+                "Invalid column count in datatable. Expected {expected}, got {got}"
+            ),
+            DataTableError::UnknownColumn(name) => write!(f, "Unknown column `{name}`"),
+            DataTableError::RowOutOfBounds { row, len } => {
+                write!(f, "Row {row} out of bounds for table with {len} rows")
+            }
+            DataTableError::ColumnOutOfBounds { column, len } => {
+                write!(
+                    f,
+                    "Column {column} out of bounds for table with {len} columns"
+                )
+            }
+            DataTableError::NotTwoColumns(len) => {
+                write!(f, "Expected a table with 2 columns, got {len}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for DataTableError {}
+
+/// An error produced when reading a [`DataTable`] from an external format,
+/// such as CSV or Markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableFormatError {
+    /// The (1-based) line on which the error occurred.
+    pub line: usize,
+    pub message: String,
+}
+
+impl TableFormatError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for TableFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.message)
+    }
+}
+
+impl core::error::Error for TableFormatError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataTable {
+    pub(crate) header: Vec<String>,
+    pub(crate) rows: Vec<Vec<String>>,
+}
+
+impl DataTable {
+    pub fn new(header: Vec<String>) -> Self {
+        Self {
+            header,
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn new_populated(header: Vec<String>, rows: Vec<Vec<String>>) -> Option<Self> {
+        if rows.iter().any(|r| r.len() != header.len()) {
+            None
+        } else {
+            Some(Self { header, rows })
+        }
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) -> Result<(), DataTableError> {
+        if row.len() == self.header.len() {
+            self.rows.push(row);
+            Ok(())
+        } else {
+            Err(DataTableError::ColumnCountMismatch {
+                expected: self.header.len(),
+                got: row.len(),
+            })
+        }
+    }
+
+    pub fn header(&self) -> &Vec<String> {
+        &self.header
+    }
+
+    pub fn rows(&self) -> &Vec<Vec<String>> {
+        &self.rows
+    }
+
+    /// The index of the column named `name`.
+    pub fn column_index(&self, name: &str) -> Result<usize, DataTableError> {
+        self.header
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| DataTableError::UnknownColumn(name.to_string()))
+    }
+
+    /// All values in the column named `name`, excluding the header.
+    pub fn column(&self, name: &str) -> Result<Vec<&str>, DataTableError> {
+        let idx = self.column_index(name)?;
+        Ok(self.rows.iter().map(|r| r[idx].as_str()).collect())
+    }
+
+    /// The value at `row` (excluding the header) and `column`.
+    pub fn cell(&self, row: usize, column: usize) -> Result<&str, DataTableError> {
+        let cells = self.rows.get(row).ok_or(DataTableError::RowOutOfBounds {
+            row,
+            len: self.rows.len(),
+        })?;
+
+        cells
+            .get(column)
+            .map(String::as_str)
+            .ok_or(DataTableError::ColumnOutOfBounds {
+                column,
+                len: self.header.len(),
+            })
+    }
+
+    /// Every row as a map from header name to cell value.
+    pub fn rows_as_maps(&self) -> Vec<BTreeMap<&str, &str>> {
+        self.rows
+            .iter()
+            .map(|row| {
+                self.header
+                    .iter()
+                    .map(String::as_str)
+                    .zip(row.iter().map(String::as_str))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Interpret a two-column table as key/value pairs.
+    ///
+    /// The header is treated as the first pair, as tables of this shape
+    /// usually do not have a real header.
+    pub fn rows_hash(&self) -> Result<BTreeMap<&str, &str>, DataTableError> {
+        if self.header.len() != 2 {
+            return Err(DataTableError::NotTwoColumns(self.header.len()));
+        }
+
+        Ok(core::iter::once(&self.header)
+            .chain(self.rows.iter())
+            .map(|r| (r[0].as_str(), r[1].as_str()))
+            .collect())
+    }
+
+    /// Swap rows and columns. The first column of this table becomes the
+    /// header of the transposed table.
+    pub fn transpose(&self) -> DataTable {
+        let all_rows: Vec<_> = core::iter::once(&self.header)
+            .chain(self.rows.iter())
+            .collect();
+
+        let mut transposed = (0..self.header.len())
+            .map(|col| all_rows.iter().map(|r| r[col].clone()).collect::<Vec<_>>());
+
+        let header = transposed.next().unwrap_or_default();
+
+        DataTable {
+            header,
+            rows: transposed.collect(),
+        }
+    }
+}
+
+impl fmt::Display for DataTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<_> = core::iter::once(&self.header)
+            .chain(self.rows.iter())
+            .map(|r| ("", r.as_slice()))
+            .collect();
+
+        write_rows(f, &rows)
+    }
+}
+
+/// Escape a cell so that it can be placed in a Gherkin table.
+pub(crate) fn escape_cell(cell: &str) -> Cow<'_, str> {
+    if !cell.contains(['\\', '|', '\n']) {
+        return Cow::Borrowed(cell);
+    }
+
+    let mut escaped = String::with_capacity(cell.len() + 2);
+    for c in cell.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '|' => escaped.push_str("\\|"),
+            '\n' => escaped.push_str("\\n"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Write `rows` as an aligned Gherkin table, one line per row, with every
+/// row preceded by its prefix.
+fn write_rows(f: &mut fmt::Formatter<'_>, rows: &[(&str, &[String])]) -> fmt::Result {
+    let rows: Vec<(&str, Vec<Cow<'_, str>>)> = rows
+        .iter()
+        .map(|(prefix, row)| (*prefix, row.iter().map(|c| escape_cell(c)).collect()))
+        .collect();
+
+    let mut widths: Vec<usize> = Vec::new();
+    for (_, row) in &rows {
+        for (idx, cell) in row.iter().enumerate() {
+            let len = cell.chars().count();
+            match widths.get_mut(idx) {
+                Some(width) => *width = (*width).max(len),
+                None => widths.push(len),
+            }
+        }
+    }
+
+    let prefix_width = rows.iter().map(|(p, _)| p.len()).max().unwrap_or(0);
+
+    for (idx, (prefix, row)) in rows.iter().enumerate() {
+        if idx != 0 {
+            writeln!(f)?;
+        }
+
+        if prefix_width != 0 {
+            write!(f, "{prefix:prefix_width$} ")?;
+        }
+
+        write!(f, "|")?;
+        for (cell, width) in row.iter().zip(&widths) {
+            let cell: &str = cell;
+            write!(f, " {cell:width$} |")?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+fn make_table(header: &[&str], rows: &[&[&str]]) -> DataTable {
+    DataTable::new_populated(
+        header.iter().map(|s| s.to_string()).collect(),
+        rows.iter()
+            .map(|r| r.iter().map(|s| s.to_string()).collect())
+            .collect(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn data_table_access() {
+    let table = make_table(
+        &["name", "age"],
+        &[&["alice", "30"], &["bob", "25"], &["carol", "41"]],
+    );
+
+    assert_eq!(table.column("age"), Ok(vec!["30", "25", "41"]));
+    assert_eq!(
+        table.column("height"),
+        Err(DataTableError::UnknownColumn("height".into()))
+    );
+
+    assert_eq!(table.cell(1, 0), Ok("bob"));
+    assert_eq!(
+        table.cell(3, 0),
+        Err(DataTableError::RowOutOfBounds { row: 3, len: 3 })
+    );
+    assert_eq!(
+        table.cell(0, 2),
+        Err(DataTableError::ColumnOutOfBounds { column: 2, len: 2 })
+    );
+
+    let maps = table.rows_as_maps();
+    assert_eq!(maps.len(), 3);
+    assert_eq!(maps[2]["name"], "carol");
+    assert_eq!(maps[2]["age"], "41");
+
+    let hash = table.rows_hash().unwrap();
+    assert_eq!(hash["name"], "age");
+    assert_eq!(hash["alice"], "30");
+
+    let transposed = table.transpose();
+    let expected = make_table(
+        &["name", "alice", "bob", "carol"],
+        &[&["age", "30", "25", "41"]],
+    );
+    assert_eq!(transposed, expected);
+    assert_eq!(transposed.transpose(), table);
+
+    let mut table = table;
+    assert_eq!(
+        table.add_row(vec!["dave".into()]),
+        Err(DataTableError::ColumnCountMismatch {
+            expected: 2,
+            got: 1
+        })
+    );
+    assert_eq!(
+        make_table(&["a", "b", "c"], &[]).rows_hash(),
+        Err(DataTableError::NotTwoColumns(3))
+    );
+}
@@ -0,0 +1,507 @@
+//! Semantic comparison of two versions of a [`Feature`].
+//!
+//! [`diff`] compares the parsed features, so changes in formatting, comments
+//! and descriptions are ignored. Scenarios are matched to scenarios and
+//! scenario outlines to scenario outlines by name; a scenario whose name
+//! changed but whose steps did not is reported as renamed.
+//!
+//! ```
+//! use gherkin::{diff::Change, Parser};
+//!
+//! let old = Parser::parse_feature("Feature: f\n  Scenario: a\n    Given x\n").unwrap();
+//! let new = Parser::parse_feature("Feature: f\n  Scenario: b\n    Given x\n").unwrap();
+//!
+//! let diff = gherkin::diff(&old, &new);
+//! assert_eq!(
+//!     diff.changes,
+//!     [Change::ScenarioRenamed { old: "a".into(), new: "b".into() }]
+//! );
+//! assert_eq!(diff.to_string(), "- Renamed scenario `a` to `b`\n");
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt;
+
+use crate::{
+    lookup::Element, render::step_keyword, DataTable, Feature, Location, Step, TableDiff,
+    TaggedScenarios,
+};
+
+/// The element of a feature a [`Change`] applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scope {
+    Feature,
+    Background,
+    /// The scenario or scenario outline with the given name, as named in the
+    /// new version.
+    Scenario(String),
+    /// The examples at position `index` of the scenario outline named
+    /// `outline`.
+    Examples {
+        outline: String,
+        index: usize,
+    },
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scope::Feature => write!(f, "Feature"),
+            Scope::Background => write!(f, "Background"),
+            Scope::Scenario(name) => write!(f, "Scenario `{name}`"),
+            Scope::Examples { outline, index } => {
+                write!(f, "Scenario `{outline}`, examples {}", index + 1)
+            }
+        }
+    }
+}
+
+/// A single difference between two versions of a feature.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    FeatureRenamed {
+        old: String,
+        new: String,
+    },
+    ScenarioAdded {
+        name: String,
+        location: Location,
+    },
+    /// A scenario that was removed, located in the old version.
+    ScenarioRemoved {
+        name: String,
+        location: Location,
+    },
+    ScenarioRenamed {
+        old: String,
+        new: String,
+    },
+    TagAdded {
+        scope: Scope,
+        tag: String,
+    },
+    TagRemoved {
+        scope: Scope,
+        tag: String,
+    },
+    StepAdded {
+        scope: Scope,
+        step: Step,
+    },
+    StepRemoved {
+        scope: Scope,
+        step: Step,
+    },
+    /// The doc string or table of a step changed. Holds the new step.
+    StepDataChanged {
+        scope: Scope,
+        step: Step,
+    },
+    /// Rows of the examples of a scenario outline were added, removed or
+    /// changed. The scope is a [`Scope::Examples`].
+    Examples {
+        scope: Scope,
+        diff: TableDiff,
+    },
+}
+
+fn write_step(f: &mut fmt::Formatter<'_>, step: &Step) -> fmt::Result {
+    write!(f, "`{} {}`", step_keyword(step.ty).1, step.description)
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::FeatureRenamed { old, new } => {
+                write!(f, "Renamed feature `{old}` to `{new}`")
+            }
+            Change::ScenarioAdded { name, .. } => write!(f, "Added scenario `{name}`"),
+            Change::ScenarioRemoved { name, .. } => write!(f, "Removed scenario `{name}`"),
+            Change::ScenarioRenamed { old, new } => {
+                write!(f, "Renamed scenario `{old}` to `{new}`")
+            }
+            Change::TagAdded { scope, tag } => write!(f, "{scope}: added tag `@{tag}`"),
+            Change::TagRemoved { scope, tag } => write!(f, "{scope}: removed tag `@{tag}`"),
+            Change::StepAdded { scope, step } => {
+                write!(f, "{scope}: added step ")?;
+                write_step(f, step)
+            }
+            Change::StepRemoved { scope, step } => {
+                write!(f, "{scope}: removed step ")?;
+                write_step(f, step)
+            }
+            Change::StepDataChanged { scope, step } => {
+                write!(f, "{scope}: changed data of step ")?;
+                write_step(f, step)
+            }
+            Change::Examples { scope, diff } => {
+                writeln!(f, "{scope}: changed rows")?;
+                writeln!(f, "  ```diff")?;
+                for line in diff.to_string().lines() {
+                    writeln!(f, "  {line}")?;
+                }
+                write!(f, "  ```")
+            }
+        }
+    }
+}
+
+/// The differences between two versions of a feature, as returned by
+/// [`diff`].
+///
+/// The [`Display`](fmt::Display) implementation renders the changes as a
+/// Markdown list, for use in review comments.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FeatureDiff {
+    pub changes: Vec<Change>,
+}
+
+impl FeatureDiff {
+    /// Whether the features are semantically equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for FeatureDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "- {change}")?;
+        }
+        Ok(())
+    }
+}
+
+fn element_name<'a>(element: &Element<'a>) -> &'a str {
+    element.name().unwrap_or_default()
+}
+
+/// Whether two steps are the same, ignoring their location.
+fn same_step(old: &Step, new: &Step) -> bool {
+    old.ty == new.ty && old.description == new.description && old.data == new.data
+}
+
+fn same_steps(old: &[Step], new: &[Step]) -> bool {
+    old.len() == new.len() && old.iter().zip(new).all(|(o, n)| same_step(o, n))
+}
+
+fn background_steps(feature: &Feature) -> &[Step] {
+    feature.background.as_ref().map_or(&[], |b| &b.steps)
+}
+
+/// Compare two versions of a feature.
+pub fn diff(old: &Feature, new: &Feature) -> FeatureDiff {
+    let mut changes = Vec::new();
+
+    let (old_name, new_name) = (
+        old.name.as_deref().unwrap_or_default(),
+        new.name.as_deref().unwrap_or_default(),
+    );
+    if old_name != new_name {
+        changes.push(Change::FeatureRenamed {
+            old: old_name.to_string(),
+            new: new_name.to_string(),
+        });
+    }
+    diff_tags(&mut changes, Scope::Feature, &old.tags, &new.tags);
+
+    diff_steps(
+        &mut changes,
+        Scope::Background,
+        background_steps(old),
+        background_steps(new),
+    );
+
+    let same_kind =
+        |old: &Element, new: &Element| old.examples().is_some() == new.examples().is_some();
+
+    let mut unmatched_old: Vec<Option<Element>> = old.elements().into_iter().map(Some).collect();
+    let mut matches: Vec<(Option<Element>, Element)> = new
+        .elements()
+        .into_iter()
+        .map(|new| {
+            let old = unmatched_old
+                .iter_mut()
+                .find(|o| {
+                    o.as_ref().is_some_and(|o| {
+                        element_name(o) == element_name(&new) && same_kind(o, &new)
+                    })
+                })
+                .and_then(Option::take);
+            (old, new)
+        })
+        .collect();
+
+    // Unmatched scenarios with the same steps were renamed.
+    for (old, new) in &mut matches {
+        if old.is_none() {
+            *old = unmatched_old
+                .iter_mut()
+                .find(|o| {
+                    o.as_ref()
+                        .is_some_and(|o| same_steps(o.steps(), new.steps()) && same_kind(o, new))
+                })
+                .and_then(Option::take);
+        }
+    }
+
+    for (old, new) in &matches {
+        let Some(old) = old else {
+            changes.push(Change::ScenarioAdded {
+                name: element_name(new).to_string(),
+                location: new.location(),
+            });
+            continue;
+        };
+
+        if element_name(old) != element_name(new) {
+            changes.push(Change::ScenarioRenamed {
+                old: element_name(old).to_string(),
+                new: element_name(new).to_string(),
+            });
+        }
+
+        let scope = Scope::Scenario(element_name(new).to_string());
+        diff_tags(&mut changes, scope.clone(), old.tags(), new.tags());
+        diff_steps(&mut changes, scope, old.steps(), new.steps());
+        diff_examples(
+            &mut changes,
+            element_name(new),
+            old.examples().unwrap_or_default(),
+            new.examples().unwrap_or_default(),
+        );
+    }
+
+    changes.extend(
+        unmatched_old
+            .into_iter()
+            .flatten()
+            .map(|old| Change::ScenarioRemoved {
+                name: element_name(&old).to_string(),
+                location: old.location(),
+            }),
+    );
+
+    FeatureDiff { changes }
+}
+
+fn diff_tags(changes: &mut Vec<Change>, scope: Scope, old: &[String], new: &[String]) {
+    for tag in old.iter().filter(|t| !new.contains(t)) {
+        changes.push(Change::TagRemoved {
+            scope: scope.clone(),
+            tag: tag.clone(),
+        });
+    }
+    for tag in new.iter().filter(|t| !old.contains(t)) {
+        changes.push(Change::TagAdded {
+            scope: scope.clone(),
+            tag: tag.clone(),
+        });
+    }
+}
+
+fn diff_steps(changes: &mut Vec<Change>, scope: Scope, old: &[Step], new: &[Step]) {
+    // Longest common subsequence of both step lists.
+    let (n, m) = (old.len(), new.len());
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if same_step(&old[i], &new[j]) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let (mut unmatched_i, mut unmatched_j) = (0, 0);
+    while i < n && j < m {
+        if same_step(&old[i], &new[j]) {
+            push_unmatched_steps(changes, &scope, &old[unmatched_i..i], &new[unmatched_j..j]);
+            i += 1;
+            j += 1;
+            unmatched_i = i;
+            unmatched_j = j;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    push_unmatched_steps(changes, &scope, &old[unmatched_i..], &new[unmatched_j..]);
+}
+
+/// Push the steps between two matching steps. A removed and an added step
+/// with the same keyword and text are reported as changed data.
+fn push_unmatched_steps(changes: &mut Vec<Change>, scope: &Scope, old: &[Step], new: &[Step]) {
+    let mut added: Vec<Option<&Step>> = new.iter().map(Some).collect();
+
+    for old in old {
+        let same_text = added
+            .iter_mut()
+            .find(|n| n.is_some_and(|n| n.ty == old.ty && n.description == old.description));
+
+        match same_text.and_then(Option::take) {
+            Some(new) => changes.push(Change::StepDataChanged {
+                scope: scope.clone(),
+                step: new.clone(),
+            }),
+            None => changes.push(Change::StepRemoved {
+                scope: scope.clone(),
+                step: old.clone(),
+            }),
+        }
+    }
+
+    changes.extend(added.into_iter().flatten().map(|new| Change::StepAdded {
+        scope: scope.clone(),
+        step: new.clone(),
+    }));
+}
+
+fn diff_examples<'a>(
+    changes: &mut Vec<Change>,
+    outline: &str,
+    old: &'a [TaggedScenarios],
+    new: &'a [TaggedScenarios],
+) {
+    let table = |examples: Option<&TaggedScenarios>| match examples {
+        Some(e) => DataTable {
+            header: e.placeholders.clone(),
+            rows: e.values.clone(),
+        },
+        None => DataTable::new(Vec::new()),
+    };
+
+    for index in 0..old.len().max(new.len()) {
+        let (old, new) = (old.get(index), new.get(index));
+
+        let scope = Scope::Examples {
+            outline: outline.to_string(),
+            index,
+        };
+        let tags = |examples: Option<&'a TaggedScenarios>| examples.map_or(&[][..], |e| e.tags());
+        diff_tags(changes, scope.clone(), tags(old), tags(new));
+
+        let diff = table(old).diff(&table(new));
+        if diff.has_changes() {
+            changes.push(Change::Examples { scope, diff });
+        }
+    }
+}
+
+#[test]
+fn feature_diff() {
+    let old = crate::Parser::parse_feature(
+        r#"@shop
+Feature: Buying
+  Background:
+    Given a shop
+
+  Scenario: paying
+    Given a banana
+    When I pay
+    Then the receipt reads
+      """
+      1 banana
+      """
+
+  Scenario: leaving
+    When I leave
+
+  @slow
+  Scenario Outline: buying many
+    When I buy <count> items
+
+    Examples:
+      | count |
+      | 1     |
+      | 2     |
+"#,
+    )
+    .unwrap();
+
+    let new = crate::Parser::parse_feature(
+        r#"@shop
+Feature: Buying
+  Background:
+    Given a shop
+
+  @smoke
+  Scenario: paying by card
+    Given a banana
+    When I pay
+    Then the receipt reads
+      """
+      1 banana
+      """
+
+  @fast
+  Scenario Outline: buying many
+    When I buy <count> items
+    Then I pay <count>
+
+    @small
+    Examples:
+      | count |
+      | 1     |
+      | 3     |
+
+  Scenario: returning
+    When I return a banana
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        diff(&old, &new).to_string(),
+        r#"- Renamed scenario `paying` to `paying by card`
+- Scenario `paying by card`: added tag `@smoke`
+- Scenario `buying many`: removed tag `@slow`
+- Scenario `buying many`: added tag `@fast`
+- Scenario `buying many`: added step `Then I pay <count>`
+- Scenario `buying many`, examples 1: added tag `@small`
+- Scenario `buying many`, examples 1: changed rows
+  ```diff
+    | count |
+    | 1     |
+  - | 2     |
+  + | 3     |
+  ```
+- Added scenario `returning`
+- Removed scenario `leaving`
+"#
+    );
+
+    // Formatting is ignored.
+    let reformatted =
+        crate::Parser::parse_feature(&crate::render::render(&new, crate::render::Style::Plain))
+            .unwrap();
+    assert!(diff(&new, &reformatted).is_empty());
+
+    let changed_data =
+        crate::Parser::parse_feature(&new.to_string().replace("1 banana\n", "2 bananas\n"))
+            .unwrap();
+    assert_eq!(
+        diff(&new, &changed_data).to_string(),
+        "- Scenario `paying by card`: changed data of step `Then the receipt reads`\n"
+    );
+
+    // A scenario and a scenario outline are not matched by name.
+    let scenario =
+        crate::Parser::parse_feature("Feature: f\n  Scenario: buying\n    Given <n> items\n")
+            .unwrap();
+    let outline = crate::Parser::parse_feature(
+        "Feature: f\n  Scenario Outline: buying\n    Given <n> items\n\n    Examples:\n      | n |\n      | 1 |\n",
+    )
+    .unwrap();
+    assert_eq!(
+        diff(&scenario, &outline).to_string(),
+        "- Added scenario `buying`\n- Removed scenario `buying`\n"
+    );
+}
@@ -0,0 +1,926 @@
+//! Generation of living documentation from a set of features.
+//!
+//! [`Docs`] renders every added feature as a page of HTML or Markdown, along
+//! with an index page that lists the features and, per tag, the elements
+//! tagged with it. The results of a test run can be overlaid by recording
+//! the status of each scenario at its `path:line` reference, where an
+//! outline row is referred to by the line of its row in the `Examples`.
+//!
+//! Pages are named after the paths of their features. Paths that would share
+//! a name, such as `a/b.feature` and `a-b.feature`, are told apart by a
+//! numeric suffix: `a-b.html` and `a-b-2.html`.
+//!
+//! ```
+//! use gherkin::{
+//!     docs::{Docs, Status},
+//!     Parser,
+//! };
+//!
+//! let feature = Parser::parse_feature(
+//!     "Feature: Buying\n  @smoke\n  Scenario: paying\n    Given a shop\n",
+//! )
+//! .unwrap();
+//!
+//! let mut docs = Docs::default();
+//! docs.add("shop/buying.feature", &feature);
+//! docs.add_result("shop/buying.feature", 3, Status::Passed);
+//!
+//! let pages = docs.html();
+//! assert_eq!(pages[0].file, "index.html");
+//! assert_eq!(pages[1].file, "shop-buying.html");
+//! assert!(pages[1].contents.contains("<section class=\"scenario passed\" id=\"line-3\">"));
+//! ```
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, fmt::Write, str::FromStr};
+
+use crate::{
+    lexer::find_placeholder,
+    lookup::Element,
+    render::{self, html_escape, step_keyword, Style, DEFAULT_CSS},
+    DataTable, Feature, Location, Step, StepData, TaggedScenarios,
+};
+
+/// Styles for the documentation pages, used in addition to
+/// [`DEFAULT_CSS`].
+pub const DOCS_CSS: &str = "\
+body { font-family: sans-serif; max-width: 60em; margin: auto; padding: 1em; }
+.tags .gherkin-tag { margin-right: 0.5em; }
+.description { color: #57606a; }
+ul.steps { list-style: none; padding-left: 1em; }
+table { border-collapse: collapse; margin: 0.5em 0; }
+th, td { border: 1px solid #d0d7de; padding: 0.2em 0.6em; }
+.status { font-size: small; padding: 0.1em 0.4em; border-radius: 0.3em; }
+.passed > h2 .status, tr.passed .status { background: #dafbe1; }
+.failed > h2 .status, tr.failed .status { background: #ffebe9; }
+.skipped > h2 .status, tr.skipped .status { background: #fff8c5; }
+";
+
+/// The result of running a scenario.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Status {
+    Passed,
+    Skipped,
+    Failed,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Passed => "passed",
+            Status::Skipped => "skipped",
+            Status::Failed => "failed",
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "passed" => Ok(Status::Passed),
+            "skipped" => Ok(Status::Skipped),
+            "failed" => Ok(Status::Failed),
+            _ => Err(format!("Unknown status `{s}`")),
+        }
+    }
+}
+
+/// A generated file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocPage {
+    /// The file name, relative to the output directory.
+    pub file: String,
+    pub contents: String,
+}
+
+/// The output format of [`Docs`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Html,
+    Markdown,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Html => "html",
+            Format::Markdown => "md",
+        }
+    }
+}
+
+/// A documentation site for a set of features.
+#[derive(Default)]
+pub struct Docs<'a> {
+    features: Vec<(&'a str, &'a Feature)>,
+    results: BTreeMap<(String, usize), Status>,
+}
+
+impl<'a> Docs<'a> {
+    /// Add `feature`, read from the file at `path`.
+    pub fn add(&mut self, path: &'a str, feature: &'a Feature) {
+        self.features.push((path, feature));
+    }
+
+    /// Record the status of the scenario at line `line` of `path`: the line
+    /// of a `Scenario` keyword, or of a row of `Examples`.
+    pub fn add_result(&mut self, path: &str, line: usize, status: Status) {
+        self.results.insert((path.to_string(), line), status);
+    }
+
+    /// The index page, `index.html`, followed by a page per feature.
+    pub fn html(&self) -> Vec<DocPage> {
+        self.pages(Format::Html)
+    }
+
+    /// The index page, `index.md`, followed by a page per feature.
+    pub fn markdown(&self) -> Vec<DocPage> {
+        self.pages(Format::Markdown)
+    }
+
+    fn pages(&self, format: Format) -> Vec<DocPage> {
+        let index = DocPage {
+            file: format!("index.{}", format.extension()),
+            contents: self.index(format),
+        };
+
+        let files = self.page_files(format);
+        let features = self
+            .features
+            .iter()
+            .zip(files)
+            .map(|((path, feature), file)| {
+                let mut page = Page {
+                    docs: self,
+                    path,
+                    format,
+                    out: String::new(),
+                };
+                page.feature(feature);
+                DocPage {
+                    file,
+                    contents: page.out,
+                }
+            });
+
+        core::iter::once(index).chain(features).collect()
+    }
+
+    /// The file names of the feature pages, in the order of the features.
+    fn page_files(&self, format: Format) -> Vec<String> {
+        let mut used = BTreeSet::from(["index".to_string()]);
+
+        self.features
+            .iter()
+            .map(|(path, _)| {
+                let stem = page_stem(path);
+                let mut file = stem.clone();
+                for n in 2.. {
+                    if used.insert(file.clone()) {
+                        break;
+                    }
+                    file = format!("{stem}-{n}");
+                }
+                format!("{file}.{}", format.extension())
+            })
+            .collect()
+    }
+
+    fn result(&self, path: &str, line: usize) -> Option<Status> {
+        self.results.get(&(path.to_string(), line)).copied()
+    }
+
+    /// The status of a scenario, or the worst status of the rows of a
+    /// scenario outline.
+    fn element_result(&self, path: &str, element: Element) -> Option<Status> {
+        element
+            .scenario_refs()
+            .filter_map(|s| self.result(path, s.location().line))
+            .max()
+    }
+
+    fn index(&self, format: Format) -> String {
+        // Per tag, the elements tagged with it, as (link, title).
+        let mut tags: BTreeMap<&str, Vec<(String, String)>> = BTreeMap::new();
+        let files = self.page_files(format);
+        for ((_, feature), file) in self.features.iter().zip(&files) {
+            let feature_name = feature.name.as_deref().unwrap_or_default();
+
+            for tag in &feature.tags {
+                let title = format!("Feature: {feature_name}");
+                tags.entry(tag).or_default().push((file.clone(), title));
+            }
+            for element in feature.elements() {
+                let link = format!("{file}#line-{}", element.location().line);
+                let title = format!("{feature_name}: {}", element.name().unwrap_or_default());
+                for tag in element.tags() {
+                    tags.entry(tag)
+                        .or_default()
+                        .push((link.clone(), title.clone()));
+                }
+            }
+        }
+
+        let mut out = String::new();
+        match format {
+            Format::Html => {
+                html_head(&mut out, "Features");
+                out.push_str("<h1>Features</h1>\n<ul>\n");
+                for ((path, feature), file) in self.features.iter().zip(&files) {
+                    out.push_str("<li><a href=\"");
+                    html_escape(&mut out, file);
+                    out.push_str("\">");
+                    html_escape(&mut out, feature.name.as_deref().unwrap_or(path));
+                    let _ = writeln!(
+                        out,
+                        "</a> ({} scenarios{})</li>",
+                        feature.total_scenario_count(),
+                        self.summary(path, feature)
+                    );
+                }
+                out.push_str("</ul>\n<h1>Tags</h1>\n");
+                for (tag, elements) in &tags {
+                    out.push_str("<h2 class=\"gherkin-tag\">@");
+                    html_escape(&mut out, tag);
+                    out.push_str("</h2>\n<ul>\n");
+                    for (link, title) in elements {
+                        out.push_str("<li><a href=\"");
+                        html_escape(&mut out, link);
+                        out.push_str("\">");
+                        html_escape(&mut out, title);
+                        out.push_str("</a></li>\n");
+                    }
+                    out.push_str("</ul>\n");
+                }
+                out.push_str("</body>\n</html>\n");
+            }
+            Format::Markdown => {
+                out.push_str("# Features\n\n");
+                for ((path, feature), file) in self.features.iter().zip(&files) {
+                    out.push_str("- [");
+                    markdown_escape(&mut out, feature.name.as_deref().unwrap_or(path));
+                    let _ = writeln!(
+                        out,
+                        "]({file}) ({} scenarios{})",
+                        feature.total_scenario_count(),
+                        self.summary(path, feature)
+                    );
+                }
+                out.push_str("\n# Tags\n");
+                for (tag, elements) in &tags {
+                    let _ = writeln!(out, "\n## {}\n", code_span(&format!("@{tag}")));
+                    for (link, title) in elements {
+                        out.push_str("- [");
+                        markdown_escape(&mut out, title);
+                        let _ = writeln!(out, "]({link})");
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// The amount of scenarios of `feature` with each status, as in
+    /// `, 2 passed, 1 failed`.
+    fn summary(&self, path: &str, feature: &Feature) -> String {
+        let mut counts: BTreeMap<Status, usize> = BTreeMap::new();
+        let scenarios = feature
+            .elements()
+            .into_iter()
+            .flat_map(Element::scenario_refs);
+        for status in scenarios.filter_map(|s| self.result(path, s.location().line)) {
+            *counts.entry(status).or_default() += 1;
+        }
+
+        counts
+            .iter()
+            .map(|(status, count)| format!(", {count} {status}"))
+            .collect()
+    }
+}
+
+/// The file name of the page of the feature at `path`, without extension.
+fn page_stem(path: &str) -> String {
+    let stem = path.strip_suffix(".feature").unwrap_or(path);
+    let slug: String = stem
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    slug.trim_matches('-').to_string()
+}
+
+/// Escape the characters of `text` that Markdown would interpret, such as
+/// emphasis and inline HTML.
+fn markdown_escape(out: &mut String, text: &str) {
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~' | '&'
+        ) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
+
+/// A run of backticks longer than any in `text`, and at least `min` long.
+fn backticks(text: &str, min: usize) -> String {
+    let longest = text
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    "`".repeat(min.max(longest + 1))
+}
+
+/// `text` as a Markdown code span.
+fn code_span(text: &str) -> String {
+    let ticks = backticks(text, 1);
+    let pad = if text.starts_with('`') || text.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+    format!("{ticks}{pad}{text}{pad}{ticks}")
+}
+
+fn html_head(out: &mut String, title: &str) {
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>");
+    html_escape(out, title);
+    out.push_str("</title>\n<style>\n");
+    out.push_str(DEFAULT_CSS);
+    out.push_str(DOCS_CSS);
+    out.push_str("</style>\n</head>\n<body>\n");
+}
+
+/// Writes the page of a single feature.
+struct Page<'a> {
+    docs: &'a Docs<'a>,
+    path: &'a str,
+    format: Format,
+    out: String,
+}
+
+impl Page<'_> {
+    fn html(&mut self) -> bool {
+        self.format == Format::Html
+    }
+
+    fn escaped(&mut self, text: &str) {
+        match self.format {
+            Format::Html => html_escape(&mut self.out, text),
+            Format::Markdown => markdown_escape(&mut self.out, text),
+        }
+    }
+
+    /// `text`, with placeholders highlighted.
+    fn text(&mut self, text: &str) {
+        let mut rest = text;
+        while let Some(range) = find_placeholder(rest) {
+            self.escaped(&rest[..range.start]);
+            if self.html() {
+                self.out.push_str("<span class=\"gherkin-placeholder\">");
+                html_escape(&mut self.out, &rest[range.clone()]);
+                self.out.push_str("</span>");
+            } else {
+                self.out.push_str(&code_span(&rest[range.clone()]));
+            }
+            rest = &rest[range.end..];
+        }
+        self.escaped(rest);
+    }
+
+    /// The start of a heading. In Markdown, `anchor` is the line the heading
+    /// can be linked to by.
+    fn heading(&mut self, level: usize, anchor: Option<usize>, keyword: &str, name: Option<&str>) {
+        if self.html() {
+            let _ = write!(self.out, "<h{level}>{keyword}");
+        } else {
+            let _ = write!(self.out, "{} ", "#".repeat(level));
+            if let Some(line) = anchor {
+                let _ = write!(self.out, "<a id=\"line-{line}\"></a>");
+            }
+            self.out.push_str(keyword);
+        }
+        if let Some(name) = name {
+            self.out.push_str(": ");
+            self.text(name);
+        }
+    }
+
+    fn end_heading(&mut self, level: usize, status: Option<Status>) {
+        if self.html() {
+            if let Some(status) = status {
+                let _ = write!(self.out, " <span class=\"status\">{status}</span>");
+            }
+            let _ = writeln!(self.out, "</h{level}>");
+        } else {
+            if let Some(status) = status {
+                let _ = write!(self.out, " ({status})");
+            }
+            self.out.push_str("\n\n");
+        }
+    }
+
+    fn tags(&mut self, tags: &[String]) {
+        if tags.is_empty() {
+            return;
+        }
+
+        if self.html() {
+            self.out.push_str("<p class=\"tags\">");
+            for tag in tags {
+                self.out.push_str("<span class=\"gherkin-tag\">@");
+                html_escape(&mut self.out, tag);
+                self.out.push_str("</span>");
+            }
+            self.out.push_str("</p>\n");
+        } else {
+            let tags: Vec<_> = tags.iter().map(|t| code_span(&format!("@{t}"))).collect();
+            let _ = writeln!(self.out, "Tags: {}\n", tags.join(" "));
+        }
+    }
+
+    fn description(&mut self, description: Option<&str>) {
+        let Some(description) = description else {
+            return;
+        };
+
+        let paragraphs = description
+            .split("\n\n")
+            .map(str::trim)
+            .filter(|p| !p.is_empty());
+        if self.html() {
+            self.out.push_str("<div class=\"description\">\n");
+            for paragraph in paragraphs {
+                self.out.push_str("<p>");
+                html_escape(&mut self.out, paragraph);
+                self.out.push_str("</p>\n");
+            }
+            self.out.push_str("</div>\n");
+        } else {
+            for paragraph in paragraphs {
+                markdown_escape(&mut self.out, paragraph);
+                self.out.push_str("\n\n");
+            }
+        }
+    }
+
+    /// A table, with a status column if any of `statuses` is known. In HTML,
+    /// rows with a location can be linked to by their line.
+    fn table(
+        &mut self,
+        header: &[String],
+        rows: &[Vec<String>],
+        locations: &[Location],
+        statuses: &[Option<Status>],
+    ) {
+        let with_status = statuses.iter().any(Option::is_some);
+
+        if !self.html() {
+            // Cells are escaped as HTML, as Markdown tables keep backslashes.
+            let cells = |row: &[String]| -> Vec<String> {
+                row.iter()
+                    .map(|cell| {
+                        let mut escaped = String::new();
+                        html_escape(&mut escaped, cell);
+                        escaped
+                    })
+                    .collect()
+            };
+
+            let mut table = DataTable::new(cells(header));
+            for (idx, row) in rows.iter().enumerate() {
+                let mut row = cells(row);
+                if with_status {
+                    row.push(statuses[idx].map(|s| s.to_string()).unwrap_or_default());
+                }
+                table.rows.push(row);
+            }
+            if with_status {
+                table.header.push("status".to_string());
+            }
+            let _ = writeln!(self.out, "{}\n", table.to_markdown().trim_end());
+            return;
+        }
+
+        self.out.push_str("<table>\n<tr>");
+        for cell in header {
+            self.out.push_str("<th>");
+            self.text(cell);
+            self.out.push_str("</th>");
+        }
+        if with_status {
+            self.out.push_str("<th>status</th>");
+        }
+        self.out.push_str("</tr>\n");
+
+        for (idx, row) in rows.iter().enumerate() {
+            self.out.push_str("<tr");
+            if let Some(location) = locations.get(idx) {
+                let _ = write!(self.out, " id=\"line-{}\"", location.line);
+            }
+            if let Some(status) = statuses.get(idx).copied().flatten() {
+                let _ = write!(self.out, " class=\"{status}\"");
+            }
+            self.out.push('>');
+            for cell in row {
+                self.out.push_str("<td>");
+                self.text(cell);
+                self.out.push_str("</td>");
+            }
+            if with_status {
+                let status = statuses[idx].map(Status::as_str).unwrap_or_default();
+                let _ = write!(self.out, "<td class=\"status\">{status}</td>");
+            }
+            self.out.push_str("</tr>\n");
+        }
+        self.out.push_str("</table>\n");
+    }
+
+    fn steps(&mut self, steps: &[Step]) {
+        if !self.html() {
+            return self.markdown_steps(steps);
+        }
+
+        self.out.push_str("<ul class=\"steps\">\n");
+        for step in steps {
+            let keyword = step_keyword(step.ty).1;
+            let _ = write!(
+                self.out,
+                "<li><span class=\"gherkin-keyword\">{keyword}</span> "
+            );
+            self.text(&step.description);
+            self.out.push('\n');
+
+            match &step.data {
+                Some(StepData::DataTable(table)) => {
+                    self.table(table.header(), table.rows(), &[], &[]);
+                }
+                Some(StepData::DocString(doc_string)) => {
+                    self.out.push_str("<pre class=\"gherkin-doc-string\">");
+                    html_escape(&mut self.out, doc_string);
+                    self.out.push_str("</pre>\n");
+                }
+                None => {}
+            }
+
+            self.out.push_str("</li>\n");
+        }
+        self.out.push_str("</ul>\n");
+    }
+
+    /// Steps as a Markdown list, with their data indented below them.
+    fn markdown_steps(&mut self, steps: &[Step]) {
+        let mut ends_with_block = false;
+
+        for step in steps {
+            let _ = write!(self.out, "- **{}** ", step_keyword(step.ty).1);
+            self.text(&step.description);
+            self.out.push('\n');
+
+            let block = match &step.data {
+                Some(StepData::DataTable(table)) => {
+                    let mut block = Page {
+                        docs: self.docs,
+                        path: self.path,
+                        format: self.format,
+                        out: String::new(),
+                    };
+                    block.table(table.header(), table.rows(), &[], &[]);
+                    block.out
+                }
+                Some(StepData::DocString(doc_string)) => {
+                    let fence = backticks(doc_string, 3);
+                    format!("{fence}\n{doc_string}\n{fence}")
+                }
+                None => {
+                    ends_with_block = false;
+                    continue;
+                }
+            };
+
+            self.out.push('\n');
+            for line in block.trim_end().lines() {
+                let _ = writeln!(self.out, "  {line}");
+            }
+            self.out.push('\n');
+            ends_with_block = true;
+        }
+
+        if !steps.is_empty() && !ends_with_block {
+            self.out.push('\n');
+        }
+    }
+
+    fn examples(&mut self, examples: &TaggedScenarios) {
+        self.heading(3, None, "Examples", examples.name());
+        self.end_heading(3, None);
+        self.tags(examples.tags());
+        self.description(examples.description());
+
+        let statuses: Vec<_> = examples
+            .row_locations()
+            .iter()
+            .map(|l| self.docs.result(self.path, l.line))
+            .collect();
+        self.table(
+            examples.placeholders(),
+            examples.values(),
+            examples.row_locations(),
+            &statuses,
+        );
+    }
+
+    fn element(&mut self, element: Element) {
+        let status = self.docs.element_result(self.path, element);
+        let (keyword, description) = match element {
+            Element::Scenario(s) => ("Scenario", &s.description),
+            Element::Outline(o) => ("Scenario Outline", &o.description),
+        };
+
+        let line = element.location().line;
+        if self.html() {
+            let class = status.map(|s| format!(" {s}")).unwrap_or_default();
+            let _ = writeln!(
+                self.out,
+                "<section class=\"scenario{class}\" id=\"line-{line}\">"
+            );
+        }
+
+        self.heading(2, Some(line), keyword, element.name());
+        self.end_heading(2, status);
+        self.tags(element.tags());
+        self.description(description.as_deref());
+        self.steps(element.steps());
+
+        for examples in element.examples().unwrap_or_default() {
+            self.examples(examples);
+        }
+
+        if self.html() {
+            self.out.push_str("</section>\n");
+        }
+    }
+
+    fn feature(&mut self, feature: &Feature) {
+        let name = feature.name.as_deref();
+        if self.html() {
+            html_head(&mut self.out, name.unwrap_or(self.path));
+            self.out
+                .push_str("<nav><a href=\"index.html\">Index</a></nav>\n");
+        } else {
+            self.out.push_str("[Index](index.md)\n\n");
+        }
+
+        self.heading(1, None, "Feature", name);
+        self.end_heading(1, None);
+        self.tags(&feature.tags);
+        self.description(feature.description.as_deref());
+
+        if let Some(background) = &feature.background {
+            if self.html() {
+                self.out.push_str("<section class=\"background\">\n");
+            }
+            self.heading(2, None, "Background", background.name.as_deref());
+            self.end_heading(2, None);
+            self.description(background.description.as_deref());
+            self.steps(&background.steps);
+            if self.html() {
+                self.out.push_str("</section>\n");
+            }
+        }
+
+        for element in feature.elements() {
+            self.element(element);
+        }
+
+        if self.html() {
+            self.out.push_str("<details>\n<summary>Source</summary>\n");
+            self.out.push_str(&render::render(feature, Style::Html));
+            self.out.push_str("\n</details>\n</body>\n</html>\n");
+        }
+    }
+}
+
+#[cfg(test)]
+const FEATURE: &str = r#"@shop
+Feature: Buying
+  Customers buy things.
+
+  Background:
+    Given a shop
+
+  @smoke
+  Scenario: paying
+    Given the following prices
+      | item   | price |
+      | banana | 1     |
+    Then the receipt reads
+      """
+      1 banana
+      """
+
+  Scenario Outline: buying <count>
+    When I buy <count> items
+
+    Examples:
+      | count |
+      | 1     |
+      | 2     |
+"#;
+
+#[test]
+fn html() {
+    let feature = crate::Parser::parse_feature(FEATURE).unwrap();
+
+    let mut docs = Docs::default();
+    docs.add("features/buying.feature", &feature);
+    docs.add_result("features/buying.feature", 9, Status::Passed);
+    docs.add_result("features/buying.feature", 23, Status::Passed);
+    docs.add_result("features/buying.feature", 24, Status::Failed);
+
+    let pages = docs.html();
+    assert_eq!(pages.len(), 2);
+
+    let index = &pages[0].contents;
+    assert!(index.contains(
+        "<li><a href=\"features-buying.html\">Buying</a> (3 scenarios, 2 passed, 1 failed)</li>"
+    ));
+    assert!(index.contains(
+        "<h2 class=\"gherkin-tag\">@smoke</h2>\n<ul>\n\
+         <li><a href=\"features-buying.html#line-9\">Buying: paying</a></li>"
+    ));
+
+    let page = &pages[1];
+    assert_eq!(page.file, "features-buying.html");
+    assert!(page.contents.contains(DEFAULT_CSS));
+    assert!(page.contents.contains("<h1>Feature: Buying</h1>"));
+    assert!(page
+        .contents
+        .contains("<div class=\"description\">\n<p>Customers buy things.</p>\n</div>"));
+    assert!(page.contents.contains(
+        "<section class=\"scenario passed\" id=\"line-9\">\n\
+         <h2>Scenario: paying <span class=\"status\">passed</span></h2>"
+    ));
+    assert!(page.contents.contains("<tr><td>banana</td><td>1</td></tr>"));
+    assert!(page
+        .contents
+        .contains("<pre class=\"gherkin-doc-string\">1 banana</pre>"));
+    assert!(page.contents.contains(
+        "<section class=\"scenario failed\" id=\"line-18\">\n\
+         <h2>Scenario Outline: buying <span class=\"gherkin-placeholder\">&lt;count&gt;</span> \
+         <span class=\"status\">failed</span></h2>"
+    ));
+    assert!(page.contents.contains(
+        "<tr id=\"line-24\" class=\"failed\"><td>2</td><td class=\"status\">failed</td></tr>"
+    ));
+    assert!(page
+        .contents
+        .contains("<details>\n<summary>Source</summary>\n<pre class=\"gherkin\">"));
+}
+
+#[test]
+fn markdown() {
+    let feature = crate::Parser::parse_feature(FEATURE).unwrap();
+
+    let mut docs = Docs::default();
+    docs.add("buying.feature", &feature);
+    docs.add_result("buying.feature", 23, Status::Passed);
+
+    let pages = docs.markdown();
+    assert_eq!(pages[0].file, "index.md");
+    assert_eq!(
+        pages[0].contents,
+        "# Features\n\n\
+         - [Buying](buying.md) (3 scenarios, 1 passed)\n\n\
+         # Tags\n\n\
+         ## `@shop`\n\n\
+         - [Feature: Buying](buying.md)\n\n\
+         ## `@smoke`\n\n\
+         - [Buying: paying](buying.md#line-9)\n"
+    );
+
+    assert_eq!(pages[1].file, "buying.md");
+    assert_eq!(
+        pages[1].contents,
+        r#"[Index](index.md)
+
+# Feature: Buying
+
+Tags: `@shop`
+
+Customers buy things.
+
+## Background
+
+- **Given** a shop
+
+## <a id="line-9"></a>Scenario: paying
+
+Tags: `@smoke`
+
+- **Given** the following prices
+
+  | item   | price |
+  | ------ | ----- |
+  | banana | 1     |
+
+- **Then** the receipt reads
+
+  ```
+  1 banana
+  ```
+
+## <a id="line-18"></a>Scenario Outline: buying `<count>` (passed)
+
+- **When** I buy `<count>` items
+
+### Examples
+
+| count | status |
+| ----- | ------ |
+| 1     | passed |
+| 2     |        |
+
+"#
+    );
+}
+
+#[test]
+fn page_files() {
+    let feature = crate::Parser::parse_feature("Feature: f\n").unwrap();
+
+    let mut docs = Docs::default();
+    for path in [
+        "a/b.feature",
+        "a-b.feature",
+        "a-b-2.feature",
+        "index.feature",
+    ] {
+        docs.add(path, &feature);
+    }
+
+    let files: Vec<_> = docs.html().into_iter().map(|p| p.file).collect();
+    assert_eq!(
+        files,
+        [
+            "index.html",
+            "a-b.html",
+            "a-b-2.html",
+            "a-b-2-2.html",
+            "index-2.html"
+        ]
+    );
+}
+
+#[test]
+fn markdown_escapes() {
+    let feature = crate::Parser::parse_feature(
+        r#"Feature: *Buying* & <more
+  Use <b>bold</b> and `code`.
+
+  @a`b
+  Scenario: paying
+    Given the code
+      """
+      ```
+      <b>
+      ```
+      """
+    And a *table*
+      | <tag> | a & b |
+"#,
+    )
+    .unwrap();
+
+    let mut docs = Docs::default();
+    docs.add("buying.feature", &feature);
+
+    let pages = docs.markdown();
+    assert!(pages[0]
+        .contents
+        .contains("- [\\*Buying\\* \\& \\<more](buying.md)"));
+    assert!(pages[0].contents.contains("## ``@a`b``"));
+
+    let page = &pages[1].contents;
+    assert!(page.contains("# Feature: \\*Buying\\* \\& \\<more\n"));
+    assert!(page.contains("Use \\<b\\>bold\\</b\\> and \\`code\\`.\n"));
+    assert!(page.contains("Tags: ``@a`b``\n"));
+    assert!(page.contains("  ````\n  ```\n  <b>\n  ```\n  ````\n"));
+    assert!(page.contains("- **And** a \\*table\\*\n"));
+    assert!(page.contains("  | &lt;tag&gt; | a &amp; b |\n"));
+}
@@ -0,0 +1,127 @@
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::path::{Component, Path, PathBuf};
+
+use crate::{DataTable, Tag};
+
+/// The tag that marks an `Examples` section as loaded from an external
+/// source, as in `@source(data/prices.csv)`.
+pub const SOURCE_TAG: &str = "source";
+
+/// Loads the table of an `Examples` section that refers to an external
+/// source instead of containing an inline table.
+///
+/// An `Examples` section refers to an external source by being tagged with
+/// `@source(<source>)`. The loader is handed `<source>` verbatim.
+pub trait ExamplesLoader {
+    fn load(&self, source: &str) -> Result<DataTable, String>;
+}
+
+impl<F> ExamplesLoader for F
+where
+    F: Fn(&str) -> Result<DataTable, String>,
+{
+    fn load(&self, source: &str) -> Result<DataTable, String> {
+        self(source)
+    }
+}
+
+/// Find the source referred to by a `@source(<source>)` tag in `tags`.
+pub(crate) fn examples_source(tags: &[String]) -> Option<&str> {
+    Tag::find(tags, SOURCE_TAG)?.value
+}
+
+/// An [`ExamplesLoader`] that reads examples from CSV (`.csv`) or Markdown
+/// (`.md`) files, relative to a base directory. Sources that are absolute or
+/// lead out of the base directory are rejected.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct FsExamplesLoader {
+    base_dir: PathBuf,
+}
+
+#[cfg(feature = "std")]
+impl FsExamplesLoader {
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_dir: base_dir.into(),
+        }
+    }
+
+    /// A loader that resolves sources relative to the directory containing
+    /// the feature file at `feature_path`.
+    pub fn for_feature(feature_path: impl AsRef<Path>) -> Self {
+        let base_dir = feature_path
+            .as_ref()
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+
+        Self::new(base_dir)
+    }
+
+    /// The path of the file that `source` refers to. Fails if `source` is
+    /// absolute or leaves the base directory.
+    pub fn path(&self, source: &str) -> Result<PathBuf, String> {
+        let mut depth = 0usize;
+        for component in Path::new(source).components() {
+            match component {
+                Component::Normal(_) => depth += 1,
+                Component::CurDir => {}
+                Component::ParentDir if depth > 0 => depth -= 1,
+                Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                    return Err(format!(
+                        "Examples source {source} is not within {}",
+                        self.base_dir.display()
+                    ));
+                }
+            }
+        }
+
+        Ok(self.base_dir.join(source))
+    }
+}
+
+#[cfg(feature = "std")]
+impl ExamplesLoader for FsExamplesLoader {
+    fn load(&self, source: &str) -> Result<DataTable, String> {
+        let path = self.path(source)?;
+
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+
+        let table = match path.extension().and_then(|e| e.to_str()) {
+            Some("csv") => DataTable::from_csv(&contents),
+            Some("md") => DataTable::from_markdown(&contents),
+            _ => return Err(format!("Unsupported examples file {}", path.display())),
+        };
+
+        table.map_err(|e| format!("Invalid examples in {}: {e}", path.display()))
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn fs_examples_loader() {
+    let dir = std::env::temp_dir().join(format!("gherkin-examples-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("data")).unwrap();
+    std::fs::write(dir.join("data/prices.csv"), "item,price\nbanana,1\n").unwrap();
+    std::fs::write(dir.join("data/prices.txt"), "").unwrap();
+
+    let loader = FsExamplesLoader::for_feature(dir.join("prices.feature"));
+
+    let table = loader.load("data/prices.csv").unwrap();
+    assert_eq!(table.header(), &["item", "price"]);
+    assert_eq!(table.rows(), &[["banana", "1"]]);
+
+    assert!(loader.load("data/prices.txt").is_err());
+    assert!(loader.load("data/missing.csv").is_err());
+
+    assert!(loader.load("./data/../data/prices.csv").is_ok());
+    assert!(loader.load("../prices.csv").is_err());
+    assert!(loader.load("data/../../prices.csv").is_err());
+    let absolute = dir.join("data/prices.csv");
+    assert!(loader.load(absolute.to_str().unwrap()).is_err());
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
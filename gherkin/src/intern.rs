@@ -0,0 +1,453 @@
+//! A compact, shared representation of features for large corpora.
+//!
+//! Step texts, tags and table cells repeat heavily across the features of a
+//! test suite. An [`Interner`] converts parsed [`Feature`]s into
+//! [`InternedFeature`]s, in which equal strings share a single `Arc<str>`
+//! allocation. Steps are reference counted as well, so expanding a
+//! [`InternedOutline`] shares every step that contains no placeholder
+//! instead of cloning it for each row of the examples.
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use gherkin::{intern::Interner, Parser};
+//!
+//! let feature = Parser::parse_feature(
+//!     "Feature: f
+//!   Scenario Outline: o
+//!     Given a shop
+//!     When I buy <count> items
+//!
+//!     Examples:
+//!       | count |
+//!       | 1     |
+//!       | 2     |
+//! ",
+//! )
+//! .unwrap();
+//!
+//! let mut interner = Interner::default();
+//! let feature = interner.feature(&feature);
+//!
+//! let scenarios: Vec<_> = feature.scenario_outlines[0].scenarios().collect();
+//! assert!(Arc::ptr_eq(&scenarios[0].steps[0], &scenarios[1].steps[0]));
+//! assert_eq!(&*scenarios[1].steps[1].description, "I buy 2 items");
+//! ```
+
+use alloc::{
+    collections::BTreeSet,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+
+use crate::{
+    scenario_outline, Background, DataTable, Feature, KeywordType, Location, Scenario,
+    ScenarioOutline, Step, StepData, StepType, TaggedScenarios,
+};
+
+/// Deduplicates strings into shared `Arc<str>`s.
+#[derive(Debug, Default)]
+pub struct Interner {
+    strings: BTreeSet<Arc<str>>,
+}
+
+impl Interner {
+    /// The shared copy of `s`.
+    pub fn intern(&mut self, s: &str) -> Arc<str> {
+        if let Some(interned) = self.strings.get(s) {
+            return interned.clone();
+        }
+
+        let interned: Arc<str> = Arc::from(s);
+        self.strings.insert(interned.clone());
+        interned
+    }
+
+    /// The amount of distinct strings.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Whether no string has been interned yet.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Convert `feature`, sharing its strings with everything interned
+    /// before.
+    pub fn feature(&mut self, feature: &Feature) -> InternedFeature {
+        InternedFeature {
+            tags: self.strings_of(&feature.tags),
+            name: self.option(&feature.name),
+            description: self.option(&feature.description),
+            background: feature.background.as_ref().map(|b| self.background(b)),
+            scenarios: feature.scenarios.iter().map(|s| self.scenario(s)).collect(),
+            scenario_outlines: feature
+                .scenario_outlines
+                .iter()
+                .map(|o| self.outline(o))
+                .collect(),
+            location: feature.location,
+        }
+    }
+
+    fn option(&mut self, s: &Option<String>) -> Option<Arc<str>> {
+        s.as_deref().map(|s| self.intern(s))
+    }
+
+    fn strings_of(&mut self, strings: &[String]) -> Vec<Arc<str>> {
+        strings.iter().map(|s| self.intern(s)).collect()
+    }
+
+    fn step(&mut self, step: &Step) -> Arc<InternedStep> {
+        let data = step.data.as_ref().map(|data| {
+            Arc::new(match data {
+                StepData::DocString(doc_string) => {
+                    InternedStepData::DocString(self.intern(doc_string))
+                }
+                StepData::DataTable(table) => InternedStepData::DataTable(self.table(table)),
+            })
+        });
+
+        Arc::new(InternedStep {
+            ty: step.ty,
+            keyword_type: step.keyword_type,
+            description: self.intern(&step.description),
+            data,
+            location: step.location,
+        })
+    }
+
+    fn steps(&mut self, steps: &[Step]) -> Vec<Arc<InternedStep>> {
+        steps.iter().map(|s| self.step(s)).collect()
+    }
+
+    fn table(&mut self, table: &DataTable) -> InternedTable {
+        InternedTable {
+            header: self.strings_of(&table.header),
+            rows: table.rows.iter().map(|r| self.strings_of(r)).collect(),
+        }
+    }
+
+    fn background(&mut self, background: &Background) -> InternedBackground {
+        InternedBackground {
+            name: self.option(&background.name),
+            description: self.option(&background.description),
+            steps: self.steps(&background.steps),
+            location: background.location,
+        }
+    }
+
+    fn scenario(&mut self, scenario: &Scenario) -> InternedScenario {
+        InternedScenario {
+            tags: self.strings_of(&scenario.tags),
+            name: self.option(&scenario.name),
+            description: self.option(&scenario.description),
+            steps: self.steps(&scenario.steps),
+            location: scenario.location,
+        }
+    }
+
+    fn examples(&mut self, examples: &TaggedScenarios) -> InternedExamples {
+        InternedExamples {
+            tags: self.strings_of(&examples.tags),
+            name: self.option(&examples.name),
+            placeholders: self.strings_of(&examples.placeholders),
+            values: examples.values.iter().map(|r| self.strings_of(r)).collect(),
+            location: examples.location,
+            row_locations: examples.row_locations.clone(),
+        }
+    }
+
+    fn outline(&mut self, outline: &ScenarioOutline) -> InternedOutline {
+        InternedOutline {
+            tags: self.strings_of(&outline.tags),
+            name: self.option(&outline.name),
+            description: self.option(&outline.description),
+            steps: self.steps(&outline.steps),
+            examples: outline.scenarios.iter().map(|e| self.examples(e)).collect(),
+            location: outline.location,
+        }
+    }
+}
+
+/// A [`DataTable`] with shared cells.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InternedTable {
+    pub header: Vec<Arc<str>>,
+    pub rows: Vec<Vec<Arc<str>>>,
+}
+
+/// The [`StepData`] of an [`InternedStep`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum InternedStepData {
+    DocString(Arc<str>),
+    DataTable(InternedTable),
+}
+
+/// A [`Step`] with shared strings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InternedStep {
+    pub ty: StepType,
+    pub keyword_type: KeywordType,
+    pub description: Arc<str>,
+    pub data: Option<Arc<InternedStepData>>,
+    pub location: Location,
+}
+
+/// A [`Background`] with shared strings and steps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InternedBackground {
+    pub name: Option<Arc<str>>,
+    pub description: Option<Arc<str>>,
+    pub steps: Vec<Arc<InternedStep>>,
+    pub location: Location,
+}
+
+/// A [`Scenario`] with shared strings and steps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InternedScenario {
+    pub tags: Vec<Arc<str>>,
+    pub name: Option<Arc<str>>,
+    pub description: Option<Arc<str>>,
+    pub steps: Vec<Arc<InternedStep>>,
+    pub location: Location,
+}
+
+/// The [`TaggedScenarios`] of an [`InternedOutline`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct InternedExamples {
+    pub tags: Vec<Arc<str>>,
+    pub name: Option<Arc<str>>,
+    pub placeholders: Vec<Arc<str>>,
+    pub values: Vec<Vec<Arc<str>>>,
+    pub location: Location,
+    pub row_locations: Vec<Location>,
+}
+
+/// A [`ScenarioOutline`] with shared strings and steps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InternedOutline {
+    pub tags: Vec<Arc<str>>,
+    pub name: Option<Arc<str>>,
+    pub description: Option<Arc<str>>,
+    pub steps: Vec<Arc<InternedStep>>,
+    pub examples: Vec<InternedExamples>,
+    pub location: Location,
+}
+
+/// Replace `from` in `text`, sharing `text` if it does not contain it.
+fn replace(text: &Arc<str>, from: &str, to: &str) -> Arc<str> {
+    if text.contains(from) {
+        Arc::from(text.replace(from, to))
+    } else {
+        text.clone()
+    }
+}
+
+impl InternedStep {
+    /// This step with the placeholders in `replacements` replaced, shared if
+    /// it contains none of them.
+    fn expand(self: &Arc<Self>, replacements: &[(String, &str)]) -> Arc<Self> {
+        let contains = |text: &str| {
+            replacements
+                .iter()
+                .any(|(from, _)| text.contains(from.as_str()))
+        };
+        let data_contains = match self.data.as_deref() {
+            Some(InternedStepData::DocString(doc_string)) => contains(doc_string),
+            Some(InternedStepData::DataTable(table)) => table
+                .header
+                .iter()
+                .chain(table.rows.iter().flatten())
+                .any(|c| contains(c)),
+            None => false,
+        };
+
+        if !contains(&self.description) && !data_contains {
+            return self.clone();
+        }
+
+        let mut step = InternedStep::clone(self);
+        for (from, to) in replacements {
+            step.description = replace(&step.description, from, to);
+        }
+        if data_contains {
+            let mut data = InternedStepData::clone(self.data.as_deref().unwrap());
+            for (from, to) in replacements {
+                match &mut data {
+                    InternedStepData::DocString(doc_string) => {
+                        *doc_string = replace(doc_string, from, to);
+                    }
+                    InternedStepData::DataTable(table) => {
+                        for cell in table
+                            .header
+                            .iter_mut()
+                            .chain(table.rows.iter_mut().flatten())
+                        {
+                            *cell = replace(cell, from, to);
+                        }
+                    }
+                }
+            }
+            step.data = Some(Arc::new(data));
+        }
+        Arc::new(step)
+    }
+}
+
+impl InternedOutline {
+    /// The scenarios for every row of every examples. Steps without
+    /// placeholders are shared by all scenarios.
+    pub fn scenarios(&self) -> impl Iterator<Item = InternedScenario> + '_ {
+        self.examples.iter().flat_map(move |examples| {
+            examples.values.iter().map(move |row| {
+                let replacements = scenario_outline::replacements(&examples.placeholders, row);
+
+                InternedScenario {
+                    tags: examples.tags.clone(),
+                    name: self.name.clone(),
+                    description: self.description.clone(),
+                    steps: self.steps.iter().map(|s| s.expand(&replacements)).collect(),
+                    location: self.location,
+                }
+            })
+        })
+    }
+}
+
+/// A [`Feature`] with shared strings and steps. Created by
+/// [`Interner::feature`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct InternedFeature {
+    pub tags: Vec<Arc<str>>,
+    pub name: Option<Arc<str>>,
+    pub description: Option<Arc<str>>,
+    pub background: Option<InternedBackground>,
+    pub scenarios: Vec<InternedScenario>,
+    pub scenario_outlines: Vec<InternedOutline>,
+    pub location: Location,
+}
+
+impl InternedFeature {
+    /// All scenarios, including those of expanded outlines.
+    pub fn scenarios(&self) -> impl Iterator<Item = InternedScenario> + '_ {
+        self.scenarios
+            .iter()
+            .cloned()
+            .chain(self.scenario_outlines.iter().flat_map(|o| o.scenarios()))
+    }
+}
+
+impl From<&InternedStep> for Step {
+    fn from(step: &InternedStep) -> Self {
+        let strings = |cells: &[Arc<str>]| cells.iter().map(|c| c.to_string()).collect();
+        let data = step.data.as_deref().map(|data| match data {
+            InternedStepData::DocString(doc_string) => StepData::DocString(doc_string.to_string()),
+            InternedStepData::DataTable(table) => StepData::DataTable(DataTable {
+                header: strings(&table.header),
+                rows: table.rows.iter().map(|r| strings(r)).collect(),
+            }),
+        });
+
+        Step {
+            ty: step.ty,
+            keyword_type: step.keyword_type,
+            description: step.description.to_string(),
+            data,
+            location: step.location,
+        }
+    }
+}
+
+impl From<&InternedScenario> for Scenario {
+    fn from(scenario: &InternedScenario) -> Self {
+        Scenario {
+            tags: scenario.tags.iter().map(|t| t.to_string()).collect(),
+            name: scenario.name.as_deref().map(String::from),
+            description: scenario.description.as_deref().map(String::from),
+            steps: scenario.steps.iter().map(|s| Step::from(&**s)).collect(),
+            location: scenario.location,
+        }
+    }
+}
+
+#[test]
+fn interned() {
+    let feature = crate::Parser::parse_feature(
+        r#"@shop
+Feature: Buying
+  Background:
+    Given a shop
+
+  @shop
+  Scenario: paying
+    Given a shop
+    Then the receipt reads
+      """
+      1 banana
+      """
+
+  Scenario Outline: buying many
+    Given a shop
+    When I buy <count> <item>
+    Then the receipt reads
+      """
+      <count> <item>
+      """
+    And the prices are
+      | item   | price |
+      | <item> | 1     |
+
+    Examples:
+      | count | item   |
+      | 1     | banana |
+      | 2     | apple  |
+"#,
+    )
+    .unwrap();
+
+    let mut interner = Interner::default();
+    let interned = interner.feature(&feature);
+
+    // Equal strings are shared, within and across features.
+    assert!(Arc::ptr_eq(
+        &interned.tags[0],
+        &interned.scenarios[0].tags[0]
+    ));
+    assert!(Arc::ptr_eq(
+        &interned.scenarios[0].steps[1].description,
+        &interned.scenario_outlines[0].steps[2].description,
+    ));
+    let Some(InternedStepData::DataTable(prices)) =
+        interned.scenario_outlines[0].steps[3].data.as_deref()
+    else {
+        panic!("expected a data table");
+    };
+    assert!(Arc::ptr_eq(
+        &prices.header[0],
+        &interned.scenario_outlines[0].examples[0].placeholders[1],
+    ));
+    let len = interner.len();
+    let again = interner.feature(&feature);
+    assert!(Arc::ptr_eq(
+        &interned.scenarios[0].steps[0].description,
+        &again.scenario_outlines[0].steps[0].description,
+    ));
+    assert_eq!(interner.len(), len);
+
+    // Expansion matches that of the feature, and shares unchanged steps.
+    let expanded: Vec<_> = interned.scenarios().collect();
+    let expected: Vec<_> = feature.scenarios().collect();
+    assert_eq!(
+        expanded.iter().map(Scenario::from).collect::<Vec<_>>(),
+        expected
+    );
+    assert!(Arc::ptr_eq(
+        &expanded[1].steps[0],
+        &interned.scenario_outlines[0].steps[0]
+    ));
+    assert!(Arc::ptr_eq(&expanded[1].steps[0], &expanded[2].steps[0]));
+    assert!(!Arc::ptr_eq(&expanded[1].steps[1], &expanded[2].steps[1]));
+}
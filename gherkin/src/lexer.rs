@@ -0,0 +1,380 @@
+//! A line-based lexer for Gherkin, producing typed tokens with their
+//! location in the input.
+//!
+//! The lexer does not validate the structure of a feature file, which makes
+//! it suitable for syntax highlighting of incomplete or invalid input.
+
+use alloc::{collections::VecDeque, vec::Vec};
+use core::ops::Range;
+
+pub use crate::parser::Keyword;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// A keyword, such as `Feature` or `Given`, excluding the colon.
+    Keyword(Keyword),
+    /// The colon following a keyword.
+    Colon,
+    /// A tag, including the leading `@`.
+    Tag,
+    /// A comment, including the leading `#`.
+    Comment,
+    /// The name following a keyword such as `Scenario:`.
+    Name,
+    /// A line of freeform description text.
+    Description,
+    /// The text of a step.
+    StepText,
+    /// A placeholder such as `<count>`, including the angle brackets.
+    Placeholder,
+    /// A `|` separating the cells of a table row.
+    TableSeparator,
+    /// The contents of a table cell, excluding surrounding whitespace.
+    TableCell,
+    /// A `"""` or ```` ``` ```` delimiter of a doc string.
+    DocStringDelimiter,
+    /// The media type following an opening doc string delimiter.
+    DocStringMediaType,
+    /// A line of doc string content, excluding its indentation.
+    DocString,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    /// The byte range of the token in the input.
+    pub span: Range<usize>,
+    /// The (1-based) line the token is on.
+    pub line: usize,
+}
+
+impl Token<'_> {
+    /// The (1-based) column, in characters, at which the token starts.
+    pub fn column(&self, input: &str) -> usize {
+        let line_start = input[..self.span.start]
+            .rfind('\n')
+            .map(|idx| idx + 1)
+            .unwrap_or(0);
+        input[line_start..self.span.start].chars().count() + 1
+    }
+}
+
+/// An iterator over the [`Token`]s of a Gherkin document.
+pub struct Lexer<'a> {
+    input: &'a str,
+    offset: usize,
+    line: usize,
+    /// The delimiter of the doc string we are currently in, if any.
+    doc_string: Option<&'static str>,
+    pending: VecDeque<Token<'a>>,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            offset: 0,
+            line: 0,
+            doc_string: None,
+            pending: VecDeque::new(),
+        }
+    }
+
+    fn push(&mut self, kind: TokenKind, line: &'a str, line_offset: usize, text: &'a str) {
+        let start = line_offset + offset_in(line, text);
+        self.pending.push_back(Token {
+            kind,
+            text,
+            span: start..start + text.len(),
+            line: self.line,
+        });
+    }
+
+    /// Push `text`, splitting out any placeholders it contains.
+    fn push_with_placeholders(
+        &mut self,
+        kind: TokenKind,
+        line: &'a str,
+        line_offset: usize,
+        text: &'a str,
+    ) {
+        let mut rest = text;
+        while let Some(range) = find_placeholder(rest) {
+            if range.start != 0 {
+                self.push(kind, line, line_offset, &rest[..range.start]);
+            }
+            self.push(
+                TokenKind::Placeholder,
+                line,
+                line_offset,
+                &rest[range.clone()],
+            );
+            rest = &rest[range.end..];
+        }
+
+        if !rest.is_empty() {
+            self.push(kind, line, line_offset, rest);
+        }
+    }
+
+    fn lex_line(&mut self, line: &'a str, line_offset: usize) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            return;
+        }
+
+        if let Some(delimiter) = self.doc_string {
+            if trimmed == delimiter {
+                self.doc_string = None;
+                self.push(TokenKind::DocStringDelimiter, line, line_offset, trimmed);
+            } else {
+                self.push_with_placeholders(TokenKind::DocString, line, line_offset, trimmed);
+            }
+            return;
+        }
+
+        if trimmed.starts_with('#') {
+            self.push(TokenKind::Comment, line, line_offset, trimmed);
+        } else if trimmed.starts_with('@') {
+            self.lex_tags(line, line_offset, trimmed);
+        } else if trimmed.starts_with('|') {
+            self.lex_table_row(line, line_offset, trimmed);
+        } else if let Some(delimiter) = ["\"\"\"", "```"]
+            .into_iter()
+            .find(|d| trimmed.starts_with(d))
+        {
+            self.doc_string = Some(delimiter);
+            self.push(
+                TokenKind::DocStringDelimiter,
+                line,
+                line_offset,
+                &trimmed[..delimiter.len()],
+            );
+
+            let media_type = trimmed[delimiter.len()..].trim();
+            if !media_type.is_empty() {
+                self.push(TokenKind::DocStringMediaType, line, line_offset, media_type);
+            }
+        } else if let Some((keyword, keyword_text, rest, _)) = Keyword::parse(trimmed, false) {
+            self.push(TokenKind::Keyword(keyword), line, line_offset, keyword_text);
+
+            if keyword.has_colon() {
+                let colon = offset_in(trimmed, keyword_text) + keyword_text.len();
+                self.push(
+                    TokenKind::Colon,
+                    line,
+                    line_offset,
+                    &trimmed[colon..colon + 1],
+                );
+            }
+
+            let rest = rest.trim_end();
+            let kind = if keyword.has_colon() {
+                TokenKind::Name
+            } else {
+                TokenKind::StepText
+            };
+            self.push_with_placeholders(kind, line, line_offset, rest);
+        } else {
+            self.push(TokenKind::Description, line, line_offset, trimmed);
+        }
+    }
+
+    fn lex_tags(&mut self, line: &'a str, line_offset: usize, trimmed: &'a str) {
+        let mut rest = trimmed;
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                break;
+            }
+
+            if rest.starts_with('#') {
+                self.push(TokenKind::Comment, line, line_offset, rest);
+                break;
+            }
+
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            self.push(TokenKind::Tag, line, line_offset, &rest[..end]);
+            rest = &rest[end..];
+        }
+    }
+
+    fn lex_table_row(&mut self, line: &'a str, line_offset: usize, trimmed: &'a str) {
+        let mut cell_start = None;
+        let mut chars = trimmed.char_indices();
+
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '|' => {
+                    if let Some(start) = cell_start.take() {
+                        let cell = trimmed[start..idx].trim();
+                        if !cell.is_empty() {
+                            self.push_with_placeholders(
+                                TokenKind::TableCell,
+                                line,
+                                line_offset,
+                                cell,
+                            );
+                        }
+                    }
+                    self.push(
+                        TokenKind::TableSeparator,
+                        line,
+                        line_offset,
+                        &trimmed[idx..idx + 1],
+                    );
+                    cell_start = Some(idx + 1);
+                }
+                '\\' => {
+                    chars.next();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            if self.offset >= self.input.len() {
+                return None;
+            }
+
+            let rest = &self.input[self.offset..];
+            let (line, len) = match rest.find('\n') {
+                Some(idx) => (&rest[..idx], idx + 1),
+                None => (rest, rest.len()),
+            };
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            let line_offset = self.offset;
+            self.offset += len;
+            self.line += 1;
+
+            self.lex_line(line, line_offset);
+        }
+
+        self.pending.pop_front()
+    }
+}
+
+/// Tokenize all of `input`.
+pub fn tokenize(input: &str) -> Vec<Token<'_>> {
+    Lexer::new(input).collect()
+}
+
+/// The byte offset of `inner` within `outer`. `inner` must be a subslice of
+/// `outer`.
+fn offset_in(outer: &str, inner: &str) -> usize {
+    inner.as_ptr() as usize - outer.as_ptr() as usize
+}
+
+/// The byte range of the first placeholder in `text`.
+pub(crate) fn find_placeholder(text: &str) -> Option<Range<usize>> {
+    let mut search_from = 0;
+    loop {
+        let start = search_from + text[search_from..].find('<')?;
+        let len = text[start + 1..].find(['<', '>'])?;
+        let end = start + 1 + len;
+
+        if text[end..].starts_with('>') && len != 0 {
+            return Some(start..end + 1);
+        }
+
+        search_from = end;
+    }
+}
+
+#[test]
+fn lexer() {
+    use Keyword::{And, Feature, Given, ScenarioOutline, Then};
+    use TokenKind::{
+        Colon, Comment, Description, DocString, DocStringDelimiter, DocStringMediaType, Name,
+        Placeholder, StepText, TableCell, TableSeparator, Tag,
+    };
+
+    const FEATURE: &str = r#"# language: en
+@smoke @slow # trailing
+Feature: Buying
+  Some description
+
+  Scenario Outline: buy <count>
+    Given I have <count> <item>s
+    And the following prices:
+      | item   | price \| eur |
+      | banana | <price>      |
+    Then I receive:
+      """json
+      {"count": <count>}
+      """
+"#;
+
+    let tokens = tokenize(FEATURE);
+    let kinds: Vec<_> = tokens.iter().map(|t| (t.kind, t.text)).collect();
+
+    assert_eq!(
+        kinds,
+        [
+            (Comment, "# language: en"),
+            (Tag, "@smoke"),
+            (Tag, "@slow"),
+            (Comment, "# trailing"),
+            (TokenKind::Keyword(Feature), "Feature"),
+            (Colon, ":"),
+            (Name, "Buying"),
+            (Description, "Some description"),
+            (TokenKind::Keyword(ScenarioOutline), "Scenario Outline"),
+            (Colon, ":"),
+            (Name, "buy "),
+            (Placeholder, "<count>"),
+            (TokenKind::Keyword(Given), "Given"),
+            (StepText, "I have "),
+            (Placeholder, "<count>"),
+            (StepText, " "),
+            (Placeholder, "<item>"),
+            (StepText, "s"),
+            (TokenKind::Keyword(And), "And"),
+            (StepText, "the following prices:"),
+            (TableSeparator, "|"),
+            (TableCell, "item"),
+            (TableSeparator, "|"),
+            (TableCell, "price \\| eur"),
+            (TableSeparator, "|"),
+            (TableSeparator, "|"),
+            (TableCell, "banana"),
+            (TableSeparator, "|"),
+            (Placeholder, "<price>"),
+            (TableSeparator, "|"),
+            (TokenKind::Keyword(Then), "Then"),
+            (StepText, "I receive:"),
+            (DocStringDelimiter, "\"\"\""),
+            (DocStringMediaType, "json"),
+            (DocString, "{\"count\": "),
+            (Placeholder, "<count>"),
+            (DocString, "}"),
+            (DocStringDelimiter, "\"\"\""),
+        ]
+    );
+
+    for token in &tokens {
+        assert_eq!(&FEATURE[token.span.clone()], token.text);
+    }
+
+    let given = tokens
+        .iter()
+        .find(|t| t.kind == TokenKind::Keyword(Given))
+        .unwrap();
+    assert_eq!(given.line, 7);
+    assert_eq!(given.column(FEATURE), 5);
+}
+
+#[test]
+fn placeholders() {
+    assert_eq!(find_placeholder("a <b> c"), Some(2..5));
+    assert_eq!(find_placeholder("a < b <c>"), Some(6..9));
+    assert_eq!(find_placeholder("a <> <b"), None);
+}
@@ -1,11 +1,84 @@
+// Building without `std` is checked by the `no-std-check` crate.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+mod builder;
+pub use builder::{
+    BackgroundBuilder, BuildError, ExamplesBuilder, FeatureBuilder, ScenarioBuilder,
+    ScenarioOutlineBuilder,
+};
+
 mod data_table;
-pub use data_table::DataTable;
+#[cfg(feature = "serde")]
+pub use data_table::DeserializeError;
+pub use data_table::{DataTable, DataTableError, RowDiff, TableDiff, TableFormatError};
+
+pub mod diff;
+
+pub mod docs;
+pub use diff::diff;
+
+mod examples_loader;
+#[cfg(feature = "std")]
+pub use examples_loader::FsExamplesLoader;
+pub use examples_loader::{ExamplesLoader, SOURCE_TAG};
+
+pub mod intern;
+
+pub mod lexer;
+
+pub mod lint;
+
+mod lookup;
+pub use lookup::ScenarioRef;
+
+#[cfg(feature = "lsp")]
+pub mod lsp;
 
 mod parser;
-pub use parser::Parser;
+pub use parser::{Document, ParseError, Parser, ParserOptions, TextEdit};
+
+pub mod render;
+
+pub mod stats;
+
+mod static_feature;
+pub use static_feature::StaticFeature;
+
+pub mod visit;
 
 mod scenario_outline;
-pub use scenario_outline::ScenarioOutline;
+pub use scenario_outline::{ScenarioOutline, TaggedScenarios};
+
+mod tag;
+pub use tag::Tag;
+
+/// A location in a feature file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Location {
+    /// The (1-based) line, or 0 for elements that were not parsed from a
+    /// file, such as those of a [built](Feature::builder) feature.
+    pub line: usize,
+    /// The (1-based) column, in characters.
+    pub column: usize,
+}
+
+impl Location {
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+
+    /// The location of the first non-whitespace character of `text`, which
+    /// is on line `line`.
+    pub(crate) fn of_line(line: usize, text: &str) -> Self {
+        let indent = text.chars().take_while(|c| c.is_whitespace()).count();
+        Self::new(line, indent + 1)
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum StepType {
@@ -17,6 +90,34 @@ pub enum StepType {
     Asterisk,
 }
 
+/// What a step does, independent of the keyword it was written with, as
+/// in the `KeywordType` of cucumber-messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum KeywordType {
+    /// A `Given` step.
+    Context,
+    /// A `When` step.
+    Action,
+    /// A `Then` step.
+    Outcome,
+    /// An `And`, `But` or `*` step that does not follow a `Given`, `When` or
+    /// `Then` step.
+    #[default]
+    Unknown,
+}
+
+impl KeywordType {
+    /// The keyword type of `ty`, ignoring any preceding steps.
+    pub fn of(ty: StepType) -> Self {
+        match ty {
+            StepType::Given => KeywordType::Context,
+            StepType::When => KeywordType::Action,
+            StepType::Then => KeywordType::Outcome,
+            StepType::And | StepType::But | StepType::Asterisk => KeywordType::Unknown,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum StepData {
     DocString(String),
@@ -29,7 +130,15 @@ impl StepData {
             StepData::DocString(value) => {
                 *value = value.replace(from, to);
             }
-            StepData::DataTable(_) => todo!(),
+            StepData::DataTable(table) => {
+                for cell in table
+                    .header
+                    .iter_mut()
+                    .chain(table.rows.iter_mut().flatten())
+                {
+                    *cell = cell.replace(from, to);
+                }
+            }
         }
     }
 }
@@ -37,16 +146,23 @@ impl StepData {
 #[derive(Debug, Clone, PartialEq)]
 pub struct Step {
     pub ty: StepType,
+    /// The effective type of the step. `And`, `But` and `*` steps take the
+    /// type of the step they follow, including the last step of the
+    /// background for the first steps of a scenario.
+    pub keyword_type: KeywordType,
     pub description: String,
     pub data: Option<StepData>,
+    pub location: Location,
 }
 
 impl Step {
     pub fn new(ty: StepType, description: String, data: Option<StepData>) -> Self {
         Self {
             ty,
+            keyword_type: KeywordType::of(ty),
             description,
             data,
+            location: Location::default(),
         }
     }
 }
@@ -57,19 +173,34 @@ pub struct Scenario {
     pub name: Option<String>,
     pub description: Option<String>,
     pub steps: Vec<Step>,
+    pub location: Location,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Background {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub steps: Vec<Step>,
+    pub location: Location,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Feature {
     pub tags: Vec<String>,
     pub name: Option<String>,
     pub description: Option<String>,
-    pub background: Vec<Step>,
+    pub background: Option<Background>,
     pub scenarios: Vec<Scenario>,
     pub scenario_outlines: Vec<ScenarioOutline>,
+    pub location: Location,
 }
 
 impl Feature {
+    /// Start building a feature from code. See [`FeatureBuilder`].
+    pub fn builder() -> FeatureBuilder {
+        FeatureBuilder::default()
+    }
+
     pub fn scenarios(&self) -> impl Iterator<Item = Scenario> + '_ {
         let scenarios = self.scenarios.clone();
 
@@ -0,0 +1,491 @@
+//! A configurable linter for feature files.
+//!
+//! [`lint`] checks a feature file against the rules enabled in a
+//! [`LintConfig`] and reports every violation as a [`Diagnostic`]. With the
+//! `toml` feature, the configuration can be read from a TOML file such as:
+//!
+//! ```toml
+//! max-steps-per-scenario = 10
+//! required-tags = ["owner"]
+//! no-trailing-whitespace = false
+//!
+//! [indentation]
+//! step = 4
+//! ```
+
+use alloc::{
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
+    format,
+    string::String,
+    vec::Vec,
+};
+use core::fmt;
+
+use crate::{
+    lexer::{Lexer, TokenKind},
+    parser::Keyword,
+    Feature, Location, ParseError, Parser, Step, StepType, Tag,
+};
+
+/// The rules to check, and their settings.
+///
+/// Every rule is enabled by default, except for `max-steps-per-scenario` and
+/// `required-tags`, which need a value to be useful.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(default, rename_all = "kebab-case", deny_unknown_fields)
+)]
+pub struct LintConfig {
+    /// Two scenarios (or scenario outlines) of a feature may not have the
+    /// same name.
+    pub no_duplicate_scenario_names: bool,
+    /// A feature must contain at least one scenario or scenario outline.
+    pub no_empty_feature: bool,
+    /// The maximum number of steps in a scenario or scenario outline.
+    pub max_steps_per_scenario: Option<usize>,
+    /// `Given` steps may not follow `When` or `Then` steps, and `When` steps
+    /// may not follow `Then` steps.
+    pub step_order: bool,
+    /// The first step of a scenario or background may not be an `And` or
+    /// `But` step.
+    pub no_leading_and: bool,
+    /// Tags (without the leading `@`) that every scenario must have, either
+    /// directly or through its feature.
+    pub required_tags: Vec<String>,
+    pub no_trailing_whitespace: bool,
+    /// Lines starting with the same kind of keyword must be indented the
+    /// same.
+    pub consistent_indentation: bool,
+    pub indentation: Indentation,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            no_duplicate_scenario_names: true,
+            no_empty_feature: true,
+            max_steps_per_scenario: None,
+            step_order: true,
+            no_leading_and: true,
+            required_tags: Vec::new(),
+            no_trailing_whitespace: true,
+            consistent_indentation: true,
+            indentation: Indentation::default(),
+        }
+    }
+}
+
+#[cfg(feature = "toml")]
+impl LintConfig {
+    /// Read a configuration from a TOML document. Missing keys keep their
+    /// default value.
+    pub fn from_toml(input: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(input)
+    }
+}
+
+/// The expected indentation, in characters, of lines starting with each
+/// kind of keyword. When not set, the first such line of a file sets the
+/// expectation for the rest of the file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize),
+    serde(default, rename_all = "kebab-case", deny_unknown_fields)
+)]
+pub struct Indentation {
+    pub feature: Option<usize>,
+    pub background: Option<usize>,
+    /// `Scenario` and `Scenario Outline`.
+    pub scenario: Option<usize>,
+    pub examples: Option<usize>,
+    pub step: Option<usize>,
+}
+
+/// A violation of a lint rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Diagnostic {
+    /// The name of the violated rule, as used in the configuration, or
+    /// `parse-error` if the file could not be parsed.
+    pub rule: &'static str,
+    pub message: String,
+    pub location: Location,
+}
+
+impl Diagnostic {
+    fn new(rule: &'static str, message: impl Into<String>, location: Location) -> Self {
+        Self {
+            rule,
+            message: message.into(),
+            location,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: [{}] {}",
+            self.location.line, self.location.column, self.rule, self.message
+        )
+    }
+}
+
+/// Check the feature file `source` against the rules enabled in `config`.
+///
+/// The diagnostics are sorted by location. If `source` cannot be parsed, the
+/// parse error is reported as a `parse-error` diagnostic, and only the rules
+/// that do not need a parsed feature are checked.
+pub fn lint(source: &str, config: &LintConfig) -> Vec<Diagnostic> {
+    lint_parsed(source, Parser::parse_feature(source).as_ref(), config)
+}
+
+/// Like [`lint`], for a `source` that was already parsed into `parsed`, for
+/// instance with an [`ExamplesLoader`](crate::ExamplesLoader).
+pub fn lint_parsed(
+    source: &str,
+    parsed: Result<&Feature, &ParseError>,
+    config: &LintConfig,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    if config.no_trailing_whitespace {
+        trailing_whitespace(source, &mut diagnostics);
+    }
+    if config.consistent_indentation {
+        indentation(source, &config.indentation, &mut diagnostics);
+    }
+
+    match parsed {
+        Ok(feature) => lint_feature(feature, config, &mut diagnostics),
+        Err(e) => diagnostics.push(Diagnostic::new(
+            "parse-error",
+            e.message.clone(),
+            e.location,
+        )),
+    }
+
+    diagnostics.sort_by_key(|d| d.location);
+    diagnostics
+}
+
+fn lint_feature(feature: &Feature, config: &LintConfig, diagnostics: &mut Vec<Diagnostic>) {
+    // Scenarios and scenario outlines, as (tags, name, steps, location).
+    let scenarios: Vec<_> = feature
+        .scenarios
+        .iter()
+        .map(|s| (&s.tags, &s.name, &s.steps, s.location))
+        .chain(
+            feature
+                .scenario_outlines
+                .iter()
+                .map(|o| (&o.tags, &o.name, &o.steps, o.location)),
+        )
+        .collect();
+
+    if config.no_empty_feature && scenarios.is_empty() {
+        diagnostics.push(Diagnostic::new(
+            "no-empty-feature",
+            "Feature has no scenarios",
+            feature.location,
+        ));
+    }
+
+    if let (true, Some(background)) = (config.no_leading_and, &feature.background) {
+        leading_and(&background.steps, "Background", diagnostics);
+    }
+
+    let mut names: BTreeMap<&str, usize> = BTreeMap::new();
+    for (tags, name, steps, location) in scenarios {
+        if let (true, Some(name)) = (config.no_duplicate_scenario_names, name) {
+            match names.entry(name) {
+                Entry::Occupied(first) => diagnostics.push(Diagnostic::new(
+                    "no-duplicate-scenario-names",
+                    format!(
+                        "Scenario name `{name}` is already used on line {}",
+                        first.get()
+                    ),
+                    location,
+                )),
+                Entry::Vacant(entry) => {
+                    entry.insert(location.line);
+                }
+            }
+        }
+
+        if let Some(max) = config.max_steps_per_scenario {
+            if steps.len() > max {
+                diagnostics.push(Diagnostic::new(
+                    "max-steps-per-scenario",
+                    format!("Scenario has {} steps, the maximum is {max}", steps.len()),
+                    location,
+                ));
+            }
+        }
+
+        if config.step_order {
+            step_order(steps, diagnostics);
+        }
+
+        if config.no_leading_and {
+            leading_and(steps, "Scenario", diagnostics);
+        }
+
+        for required in &config.required_tags {
+            if !tags
+                .iter()
+                .chain(&feature.tags)
+                .any(|t| Tag::parse(t).name == required)
+            {
+                diagnostics.push(Diagnostic::new(
+                    "required-tags",
+                    format!("Scenario is missing the required tag `@{required}`"),
+                    location,
+                ));
+            }
+        }
+    }
+}
+
+fn step_order(steps: &[Step], diagnostics: &mut Vec<Diagnostic>) {
+    let mut current: Option<StepType> = None;
+    for step in steps {
+        let ty = match step.ty {
+            ty @ (StepType::Given | StepType::When | StepType::Then) => ty,
+            StepType::And | StepType::But | StepType::Asterisk => continue,
+        };
+
+        let order = |ty| match ty {
+            StepType::Given => 0,
+            StepType::When => 1,
+            _ => 2,
+        };
+
+        if let Some(previous) = current.filter(|p| order(*p) > order(ty)) {
+            diagnostics.push(Diagnostic::new(
+                "step-order",
+                format!("`{ty:?}` step follows a `{previous:?}` step"),
+                step.location,
+            ));
+        }
+        current = Some(ty);
+    }
+}
+
+fn leading_and(steps: &[Step], parent: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if let Some(first) = steps.first() {
+        if matches!(first.ty, StepType::And | StepType::But) {
+            diagnostics.push(Diagnostic::new(
+                "no-leading-and",
+                format!("{parent} starts with an `{:?}` step", first.ty),
+                first.location,
+            ));
+        }
+    }
+}
+
+fn trailing_whitespace(source: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for (idx, line) in source.lines().enumerate() {
+        let trimmed = line.trim_end();
+        if trimmed.len() != line.len() {
+            diagnostics.push(Diagnostic::new(
+                "no-trailing-whitespace",
+                "Trailing whitespace",
+                Location::new(idx + 1, trimmed.chars().count() + 1),
+            ));
+        }
+    }
+}
+
+fn indentation(source: &str, expected: &Indentation, diagnostics: &mut Vec<Diagnostic>) {
+    let mut expected: BTreeMap<&str, usize> = [
+        ("Feature", expected.feature),
+        ("Background", expected.background),
+        ("Scenario", expected.scenario),
+        ("Examples", expected.examples),
+        ("step", expected.step),
+    ]
+    .into_iter()
+    .filter_map(|(kind, indent)| Some((kind, indent?)))
+    .collect();
+
+    // The lexer does not know whether a line is part of a description, so
+    // only the first keyword of each line is considered, and lines that the
+    // parser would treat as free text are not excluded.
+    let mut seen_lines = BTreeSet::new();
+    for token in Lexer::new(source) {
+        let TokenKind::Keyword(keyword) = token.kind else {
+            continue;
+        };
+        if !seen_lines.insert(token.line) {
+            continue;
+        }
+
+        let kind = match keyword {
+            Keyword::Feature => "Feature",
+            Keyword::Background => "Background",
+            Keyword::Scenario | Keyword::ScenarioOutline => "Scenario",
+            Keyword::Scenarios => "Examples",
+            Keyword::Given
+            | Keyword::When
+            | Keyword::Then
+            | Keyword::And
+            | Keyword::But
+            | Keyword::Asterisk => "step",
+        };
+
+        let indent = token.column(source) - 1;
+        let expected = *expected.entry(kind).or_insert(indent);
+        if indent != expected {
+            diagnostics.push(Diagnostic::new(
+                "consistent-indentation",
+                format!("Expected {kind} to be indented by {expected}, found {indent}"),
+                Location::new(token.line, indent + 1),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+fn rules(source: &str, config: &LintConfig) -> Vec<(&'static str, usize)> {
+    lint(source, config)
+        .into_iter()
+        .map(|d| (d.rule, d.location.line))
+        .collect()
+}
+
+#[test]
+fn clean() {
+    let source = "Feature: clean
+  Background:
+    Given a shop
+
+  Scenario: buying
+    Given a banana
+    And an apple
+    When I pay
+    Then I have a receipt
+";
+    assert_eq!(lint(source, &LintConfig::default()), []);
+}
+
+#[test]
+fn scenario_rules() {
+    let source = "@owner
+Feature: rules
+  Scenario: one
+    And a
+    Then b
+    When c
+
+  @slow
+  Scenario: one
+    Given a
+    Given b
+";
+    let config = LintConfig {
+        max_steps_per_scenario: Some(2),
+        required_tags: vec!["owner".into(), "slow".into()],
+        ..LintConfig::default()
+    };
+
+    assert_eq!(
+        rules(source, &config),
+        [
+            ("max-steps-per-scenario", 3),
+            ("required-tags", 3),
+            ("no-leading-and", 4),
+            ("step-order", 6),
+            ("no-duplicate-scenario-names", 9),
+        ]
+    );
+
+    // Tags with a value count by their name.
+    let source = "@owner(team-x)\nFeature: rules\n  @slow=10\n  Scenario: one\n    Given a\n";
+    assert_eq!(rules(source, &config), []);
+}
+
+#[test]
+fn text_rules() {
+    let source = "Feature: text \n  Scenario: a\n    Given a\n   Scenario: b\n    Given b\n";
+    assert_eq!(
+        lint(source, &LintConfig::default()),
+        [
+            Diagnostic::new(
+                "no-trailing-whitespace",
+                "Trailing whitespace",
+                Location::new(1, 14)
+            ),
+            Diagnostic::new(
+                "consistent-indentation",
+                "Expected Scenario to be indented by 2, found 3",
+                Location::new(4, 4)
+            ),
+        ]
+    );
+
+    let config = LintConfig {
+        indentation: Indentation {
+            step: Some(2),
+            ..Indentation::default()
+        },
+        ..LintConfig::default()
+    };
+    assert_eq!(
+        rules(source, &config),
+        [
+            ("no-trailing-whitespace", 1),
+            ("consistent-indentation", 3),
+            ("consistent-indentation", 4),
+            ("consistent-indentation", 5),
+        ]
+    );
+}
+
+#[test]
+fn empty_and_invalid() {
+    assert_eq!(
+        rules("Feature: empty\n", &LintConfig::default()),
+        [("no-empty-feature", 1)]
+    );
+    assert_eq!(
+        rules("Scenario: a\n", &LintConfig::default()),
+        [("parse-error", 1)]
+    );
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml_config() {
+    let config = LintConfig::from_toml(
+        r#"
+max-steps-per-scenario = 10
+required-tags = ["owner"]
+no-trailing-whitespace = false
+
+[indentation]
+step = 4
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config,
+        LintConfig {
+            max_steps_per_scenario: Some(10),
+            required_tags: vec!["owner".into()],
+            no_trailing_whitespace: false,
+            indentation: Indentation {
+                step: Some(4),
+                ..Indentation::default()
+            },
+            ..LintConfig::default()
+        }
+    );
+
+    assert!(LintConfig::from_toml("no-such-rule = true").is_err());
+}
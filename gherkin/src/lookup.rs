@@ -0,0 +1,238 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{Feature, Location, Scenario, ScenarioOutline, Step, StepData, TaggedScenarios};
+
+/// A scenario of a [`Feature`]: either a plain scenario, or one row of the
+/// examples of a scenario outline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScenarioRef<'a> {
+    Scenario(&'a Scenario),
+    OutlineRow {
+        outline: &'a ScenarioOutline,
+        examples: &'a TaggedScenarios,
+        /// The index of the row in the values of `examples`.
+        row: usize,
+    },
+}
+
+impl ScenarioRef<'_> {
+    /// The scenario, with placeholders replaced for outline rows.
+    pub fn scenario(&self) -> Scenario {
+        match self {
+            ScenarioRef::Scenario(scenario) => (*scenario).clone(),
+            ScenarioRef::OutlineRow {
+                outline,
+                examples,
+                row,
+            } => outline.expand(examples, *row),
+        }
+    }
+
+    /// The location of the `Scenario` keyword, or of the examples row.
+    pub fn location(&self) -> Location {
+        match self {
+            ScenarioRef::Scenario(scenario) => scenario.location,
+            ScenarioRef::OutlineRow { examples, row, .. } => examples.row_locations()[*row],
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            ScenarioRef::Scenario(scenario) => scenario.name.as_deref(),
+            ScenarioRef::OutlineRow { outline, .. } => outline.name.as_deref(),
+        }
+    }
+}
+
+/// A scenario or scenario outline of a [`Feature`], as written in the file.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Element<'a> {
+    Scenario(&'a Scenario),
+    Outline(&'a ScenarioOutline),
+}
+
+impl<'a> Element<'a> {
+    pub(crate) fn name(&self) -> Option<&'a str> {
+        match self {
+            Element::Scenario(s) => s.name.as_deref(),
+            Element::Outline(o) => o.name.as_deref(),
+        }
+    }
+
+    pub(crate) fn tags(&self) -> &'a [String] {
+        match self {
+            Element::Scenario(s) => &s.tags,
+            Element::Outline(o) => &o.tags,
+        }
+    }
+
+    pub(crate) fn steps(&self) -> &'a [Step] {
+        match self {
+            Element::Scenario(s) => &s.steps,
+            Element::Outline(o) => &o.steps,
+        }
+    }
+
+    /// The examples of a scenario outline.
+    pub(crate) fn examples(&self) -> Option<&'a [TaggedScenarios]> {
+        match self {
+            Element::Scenario(_) => None,
+            Element::Outline(o) => Some(&o.scenarios),
+        }
+    }
+
+    /// The location of the `Scenario` or `Scenario Outline` keyword.
+    pub(crate) fn location(&self) -> Location {
+        match self {
+            Element::Scenario(s) => s.location,
+            Element::Outline(o) => o.location,
+        }
+    }
+
+    /// The scenario, or every row of the scenario outline.
+    pub(crate) fn scenario_refs(self) -> impl Iterator<Item = ScenarioRef<'a>> {
+        let (scenario, outline) = match self {
+            Element::Scenario(s) => (Some(ScenarioRef::Scenario(s)), None),
+            Element::Outline(o) => (None, Some(o)),
+        };
+
+        let rows = outline.into_iter().flat_map(|outline| {
+            outline.scenarios.iter().flat_map(move |examples| {
+                (0..examples.len()).map(move |row| ScenarioRef::OutlineRow {
+                    outline,
+                    examples,
+                    row,
+                })
+            })
+        });
+
+        scenario.into_iter().chain(rows)
+    }
+}
+
+/// The last line of `step`, including its doc string or table.
+fn last_line(step: &Step) -> usize {
+    let data_lines = match &step.data {
+        Some(StepData::DocString(text)) => text.lines().count() + 2,
+        Some(StepData::DataTable(table)) => table.rows().len() + 1,
+        None => 0,
+    };
+    step.location.line + data_lines
+}
+
+impl Feature {
+    /// The scenarios and scenario outlines, in the order of the file.
+    pub(crate) fn elements(&self) -> Vec<Element<'_>> {
+        let mut elements: Vec<_> = self
+            .scenarios
+            .iter()
+            .map(Element::Scenario)
+            .chain(self.scenario_outlines.iter().map(Element::Outline))
+            .collect();
+        elements.sort_by_key(Element::location);
+        elements
+    }
+
+    /// The scenario at line `line`, as in a `path.feature:17` reference.
+    ///
+    /// A plain scenario is found from any line from its `Scenario` keyword
+    /// to the end of its last step. A row of a scenario outline is found
+    /// from its own line in the `Examples` table, and the first row also from
+    /// the `Scenario Outline` keyword. Scenarios on line 0, such as those of
+    /// a [built](Feature::builder) feature, are never found.
+    pub fn scenario_at_line(&self, line: usize) -> Option<ScenarioRef<'_>> {
+        let element = self
+            .elements()
+            .into_iter()
+            .filter(|e| e.location().line > 0)
+            .take_while(|e| e.location().line <= line)
+            .last()?;
+
+        let outline = match element {
+            Element::Scenario(scenario) => {
+                let end = scenario
+                    .steps
+                    .last()
+                    .map_or(scenario.location.line, last_line);
+                return (line <= end).then_some(ScenarioRef::Scenario(scenario));
+            }
+            Element::Outline(outline) => outline,
+        };
+
+        outline.scenarios.iter().find_map(|examples| {
+            let row = if line == outline.location.line {
+                (!examples.is_empty()).then_some(0)?
+            } else {
+                examples
+                    .row_locations()
+                    .iter()
+                    .position(|l| l.line == line)?
+            };
+            Some(ScenarioRef::OutlineRow {
+                outline,
+                examples,
+                row,
+            })
+        })
+    }
+
+    /// All scenarios whose name contains `name_pattern`, including every
+    /// row of matching scenario outlines, in the order of the file.
+    pub fn find_scenarios(&self, name_pattern: &str) -> Vec<ScenarioRef<'_>> {
+        self.elements()
+            .into_iter()
+            .filter(|e| e.name().is_some_and(|n| n.contains(name_pattern)))
+            .flat_map(Element::scenario_refs)
+            .collect()
+    }
+}
+
+#[test]
+fn lookup() {
+    let feature = crate::Parser::parse_feature(
+        r#"Feature: lookup
+
+  Scenario: buying
+    Given a shop
+
+  Scenario Outline: buying many
+    Given <count> items
+
+    Examples:
+      | count |
+      | 1     |
+
+      | 2     |
+
+  Scenario: paying
+    Given a till
+      | coin |
+"#,
+    )
+    .unwrap();
+
+    let at = |line| feature.scenario_at_line(line).map(|s| s.scenario());
+
+    assert_eq!(at(1), None);
+    assert_eq!(at(3).unwrap().name.unwrap(), "buying");
+    assert_eq!(at(4).unwrap().name.unwrap(), "buying");
+    assert_eq!(at(5), None);
+    assert_eq!(at(6).unwrap().steps[0].description, "1 items");
+    assert_eq!(at(7), None);
+    assert_eq!(at(10), None);
+    assert_eq!(at(11).unwrap().steps[0].description, "1 items");
+    assert_eq!(at(13).unwrap().steps[0].description, "2 items");
+    assert_eq!(at(16).unwrap().name.unwrap(), "paying");
+    assert_eq!(at(17).unwrap().name.unwrap(), "paying");
+    assert_eq!(at(18), None);
+    assert_eq!(at(100), None);
+
+    let found: Vec<_> = feature
+        .find_scenarios("buying")
+        .iter()
+        .map(|s| s.location().line)
+        .collect();
+    assert_eq!(found, [3, 11, 13]);
+    assert_eq!(feature.find_scenarios("ing").len(), 4);
+    assert_eq!(feature.find_scenarios("selling").len(), 0);
+}
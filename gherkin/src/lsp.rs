@@ -0,0 +1,711 @@
+//! A language server for feature files, speaking the Language Server
+//! Protocol (LSP) over any reader and writer.
+//!
+//! The server supports:
+//!
+//! - Diagnostics from the parser and the [linter](crate::lint), published
+//!   whenever a document is opened or changed.
+//! - Document symbols for the feature, its background, scenarios, scenario
+//!   outlines and examples.
+//! - Folding ranges for the same sections.
+//! - Formatting with the plain [renderer](crate::render). As the renderer
+//!   does not keep comments, documents with comments are left unchanged.
+//! - Completion of keywords, and of the texts of the steps in all open
+//!   documents after a step keyword.
+//!
+//! Documents are synchronized in full on every change. The `@source`
+//! examples of `file:` documents are loaded relative to the document.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{self, BufRead, Write},
+    path::PathBuf,
+};
+
+use serde_json::{json, Value};
+
+use crate::{
+    lexer::{Lexer, TokenKind},
+    lint::{lint_parsed, LintConfig},
+    parser::Keyword,
+    render::{render, Style},
+    Feature, FsExamplesLoader, Location, ParserOptions,
+};
+
+/// The keywords offered by completion.
+const KEYWORDS: &[&str] = &[
+    "Feature: ",
+    "Background: ",
+    "Scenario: ",
+    "Scenario Outline: ",
+    "Examples: ",
+    "Given ",
+    "When ",
+    "Then ",
+    "And ",
+    "But ",
+];
+
+/// The error code of a message that is not valid JSON.
+const PARSE_ERROR: i64 = -32700;
+
+/// The error code of a request for an unsupported method.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// Run a language server that reads messages from `input` and writes
+/// messages to `output`, until it receives an `exit` notification or
+/// `input` ends.
+pub fn serve(mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut server = Server::default();
+
+    while let Some(body) = read_body(&mut input)? {
+        let message: Value = match serde_json::from_slice(&body) {
+            Ok(message) => message,
+            Err(e) => {
+                let error = json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": { "code": PARSE_ERROR, "message": e.to_string() },
+                });
+                write_message(&mut output, &error)?;
+                continue;
+            }
+        };
+
+        if message["method"] == "exit" {
+            break;
+        }
+
+        for response in server.handle(&message) {
+            write_message(&mut output, &response)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Read a message with its `Content-Length` header. Returns `None` at the
+/// end of `input`.
+pub fn read_message(input: &mut impl BufRead) -> io::Result<Option<Value>> {
+    match read_body(input)? {
+        Some(body) => Ok(Some(serde_json::from_slice(&body)?)),
+        None => Ok(None),
+    }
+}
+
+/// Read the body of a message, without parsing it. Returns `None` at the end
+/// of `input`.
+fn read_body(input: &mut impl BufRead) -> io::Result<Option<Vec<u8>>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            let value = value.trim().parse().map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidData, "Invalid Content-Length")
+            })?;
+            length = Some(value);
+        }
+    }
+
+    let length = length
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Missing Content-Length"))?;
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+
+    Ok(Some(body))
+}
+
+/// Write `message` with its `Content-Length` header.
+pub fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let body = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    output.flush()
+}
+
+/// The state of a language server: the contents of the open documents.
+#[derive(Debug, Default)]
+pub struct Server {
+    documents: BTreeMap<String, String>,
+}
+
+impl Server {
+    /// Handle a message from the client, returning the messages to send
+    /// back.
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let Some(method) = message["method"].as_str() else {
+            // A response to a request we never send.
+            return Vec::new();
+        };
+        let params = &message["params"];
+
+        if let Some(id) = message.get("id") {
+            let response = match self.request(method, params) {
+                Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                Err(message) => json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": METHOD_NOT_FOUND, "message": message },
+                }),
+            };
+            return vec![response];
+        }
+
+        self.notification(method, params)
+    }
+
+    fn request(&self, method: &str, params: &Value) -> Result<Value, String> {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        let document = || self.documents.get(uri).map(String::as_str);
+
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "documentSymbolProvider": true,
+                    "foldingRangeProvider": true,
+                    "documentFormattingProvider": true,
+                    "completionProvider": {},
+                },
+                "serverInfo": { "name": "gherkin-lsp" },
+            }),
+            "shutdown" => Value::Null,
+            "textDocument/documentSymbol" => document()
+                .and_then(|text| Some(document_symbols(text, &parse(uri, text)?)))
+                .unwrap_or_default(),
+            "textDocument/foldingRange" => document()
+                .and_then(|text| Some(folding_ranges(text, &parse(uri, text)?)))
+                .unwrap_or_default(),
+            "textDocument/formatting" => document()
+                .and_then(|text| Some(formatting(text, &parse(uri, text)?)))
+                .unwrap_or_default(),
+            "textDocument/completion" => document()
+                .map(|text| self.completion(text, &params["position"]))
+                .unwrap_or_default(),
+            _ => return Err(format!("Unsupported method {method}")),
+        };
+
+        Ok(result)
+    }
+
+    fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let uri = params["textDocument"]["uri"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+
+        match method {
+            "textDocument/didOpen" => {
+                let text = params["textDocument"]["text"].as_str().unwrap_or_default();
+                self.documents.insert(uri.clone(), text.to_string());
+            }
+            "textDocument/didChange" => {
+                let changes = params["contentChanges"].as_array();
+                let Some(text) = changes.and_then(|c| c.last()?["text"].as_str()) else {
+                    return Vec::new();
+                };
+                self.documents.insert(uri.clone(), text.to_string());
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(&uri);
+                return vec![publish_diagnostics(&uri, Vec::new())];
+            }
+            _ => return Vec::new(),
+        }
+
+        let text = &self.documents[&uri];
+        let loader = examples_loader(&uri);
+        let parsed = ParserOptions::strict().examples_loader(&loader).parse(text);
+        let diagnostics = lint_parsed(text, parsed.as_ref(), &LintConfig::default())
+            .into_iter()
+            .map(|d| {
+                json!({
+                    "range": line_range(text, d.location),
+                    "severity": if d.rule == "parse-error" { 1 } else { 2 },
+                    "source": "gherkin",
+                    "code": d.rule,
+                    "message": d.message,
+                })
+            })
+            .collect();
+
+        vec![publish_diagnostics(&uri, diagnostics)]
+    }
+
+    fn completion(&self, text: &str, position: &Value) -> Value {
+        let line_idx = position["line"].as_u64().unwrap_or_default() as usize;
+        let line = text.lines().nth(line_idx).unwrap_or_default();
+        let character = position["character"].as_u64().unwrap_or_default() as usize;
+        let prefix = &line[..byte_offset(line, character)];
+        let trimmed = prefix.trim_start();
+
+        let edit = |start: &str, label: &str, kind: u32| {
+            json!({
+                "label": label,
+                "kind": kind,
+                "textEdit": {
+                    "range": {
+                        "start": { "line": line_idx, "character": utf16_len(start) },
+                        "end": { "line": line_idx, "character": character },
+                    },
+                    "newText": label,
+                },
+            })
+        };
+
+        let step_text_start = Keyword::parse(trimmed, false)
+            .filter(|(keyword, ..)| !keyword.has_colon())
+            .and_then(|(_, keyword, ..)| {
+                let after = &trimmed[keyword.len()..];
+                let space = after.chars().next().filter(|c| c.is_whitespace())?;
+                Some(&prefix[..prefix.len() - after.len() + space.len_utf8()])
+            });
+
+        let items: Vec<_> = match step_text_start {
+            Some(start) => self
+                .step_texts()
+                .into_iter()
+                .map(|text| edit(start, &text, 1))
+                .collect(),
+            None => {
+                let start = &prefix[..prefix.len() - trimmed.len()];
+                KEYWORDS
+                    .iter()
+                    .map(|keyword| edit(start, keyword, 14))
+                    .collect()
+            }
+        };
+
+        Value::Array(items)
+    }
+
+    /// The texts of the steps in all open documents that can be parsed.
+    fn step_texts(&self) -> BTreeSet<String> {
+        let mut texts = BTreeSet::new();
+        for feature in self.documents.iter().filter_map(|(u, t)| parse(u, t)) {
+            let steps = feature
+                .background
+                .iter()
+                .flat_map(|b| &b.steps)
+                .chain(feature.scenarios.iter().flat_map(|s| &s.steps))
+                .chain(feature.scenario_outlines.iter().flat_map(|o| &o.steps));
+            texts.extend(steps.map(|s| s.description.clone()));
+        }
+
+        texts
+    }
+}
+
+fn parse(uri: &str, text: &str) -> Option<Feature> {
+    let loader = examples_loader(uri);
+    ParserOptions::lenient()
+        .examples_loader(&loader)
+        .parse(text)
+        .ok()
+}
+
+/// A loader for the `@source` examples of the document at `uri`. Documents
+/// that are not `file:` URIs load from the working directory.
+fn examples_loader(uri: &str) -> FsExamplesLoader {
+    match uri.strip_prefix("file://") {
+        Some(path) => FsExamplesLoader::for_feature(PathBuf::from(percent_decode(path))),
+        None => FsExamplesLoader::new("."),
+    }
+}
+
+/// Decode the `%XX` escapes of a URI path.
+fn percent_decode(path: &str) -> String {
+    let mut bytes = Vec::new();
+    let mut rest = path.as_bytes();
+
+    while let Some((&b, after)) = rest.split_first() {
+        let hex = after.get(..2).and_then(|h| core::str::from_utf8(h).ok());
+        match hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+            Some(decoded) if b == b'%' => {
+                bytes.push(decoded);
+                rest = &after[2..];
+            }
+            _ => {
+                bytes.push(b);
+                rest = after;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    })
+}
+
+/// A section of a feature file, such as a scenario, spanning the (0-based)
+/// lines `start..=end`.
+struct Section {
+    kind: u32,
+    name: String,
+    start: usize,
+    end: usize,
+    location: Location,
+    children: Vec<Section>,
+}
+
+fn sections(text: &str, feature: &Feature) -> Section {
+    let lines: Vec<_> = text.lines().collect();
+
+    let section = |keyword: &str, name: &Option<String>, location: Location, kind| Section {
+        kind,
+        name: match name {
+            Some(name) => format!("{keyword}: {name}"),
+            None => keyword.to_string(),
+        },
+        start: location.line - 1,
+        end: location.line - 1,
+        location,
+        children: Vec::new(),
+    };
+
+    let mut children: Vec<_> = feature
+        .background
+        .iter()
+        .map(|b| section("Background", &b.name, b.location, 9))
+        .chain(
+            feature
+                .scenarios
+                .iter()
+                .map(|s| section("Scenario", &s.name, s.location, 6)),
+        )
+        .chain(feature.scenario_outlines.iter().map(|o| {
+            let mut outline = section("Scenario Outline", &o.name, o.location, 6);
+            outline.children = o
+                .scenarios
+                .iter()
+                .map(|e| section("Examples", &e.name, e.location, 18))
+                .collect();
+            outline
+        }))
+        .collect();
+    children.sort_by_key(|s| s.start);
+
+    let mut feature = section("Feature", &feature.name, feature.location, 2);
+    feature.children = children;
+    set_ends(&lines, std::slice::from_mut(&mut feature), lines.len());
+    feature
+}
+
+/// Set the end of each of `sections` to the last line with content before
+/// the next section, or before `until`.
+fn set_ends(lines: &[&str], sections: &mut [Section], until: usize) {
+    let starts: Vec<_> = sections.iter().map(|s| s.start).skip(1).collect();
+    for (section, next) in sections.iter_mut().zip(starts.into_iter().chain([until])) {
+        section.end = (section.start..next)
+            .rev()
+            .find(|&idx| {
+                let line = lines[idx].trim();
+                !line.is_empty() && !line.starts_with(['@', '#'])
+            })
+            .unwrap_or(section.start);
+        set_ends(lines, &mut section.children, section.end + 1);
+    }
+}
+
+fn document_symbols(text: &str, feature: &Feature) -> Value {
+    fn symbol(text: &str, section: &Section) -> Value {
+        let lines: Vec<_> = text.lines().collect();
+        json!({
+            "name": section.name,
+            "kind": section.kind,
+            "range": {
+                "start": { "line": section.start, "character": 0 },
+                "end": { "line": section.end, "character": utf16_len(lines[section.end]) },
+            },
+            "selectionRange": line_range(text, section.location),
+            "children": section.children.iter().map(|c| symbol(text, c)).collect::<Vec<_>>(),
+        })
+    }
+
+    json!([symbol(text, &sections(text, feature))])
+}
+
+fn folding_ranges(text: &str, feature: &Feature) -> Value {
+    fn push(section: &Section, ranges: &mut Vec<Value>) {
+        if section.end > section.start {
+            ranges.push(json!({ "startLine": section.start, "endLine": section.end }));
+        }
+        section.children.iter().for_each(|c| push(c, ranges));
+    }
+
+    let mut ranges = Vec::new();
+    push(&sections(text, feature), &mut ranges);
+    Value::Array(ranges)
+}
+
+fn formatting(text: &str, feature: &Feature) -> Value {
+    // Rendering would drop the comments.
+    if Lexer::new(text).any(|t| t.kind == TokenKind::Comment) {
+        return json!([]);
+    }
+
+    let formatted = render(feature, Style::Plain);
+    if formatted == text {
+        return json!([]);
+    }
+
+    let last_line = text.split('\n').count() - 1;
+    let last_character = utf16_len(text.rsplit('\n').next().unwrap_or_default());
+    json!([{
+        "range": {
+            "start": { "line": 0, "character": 0 },
+            "end": { "line": last_line, "character": last_character },
+        },
+        "newText": formatted,
+    }])
+}
+
+/// The range from `location` to the end of its line.
+fn line_range(text: &str, location: Location) -> Value {
+    let line = text.lines().nth(location.line - 1).unwrap_or_default();
+    let start: String = line.chars().take(location.column - 1).collect();
+    json!({
+        "start": { "line": location.line - 1, "character": utf16_len(&start) },
+        "end": { "line": location.line - 1, "character": utf16_len(line) },
+    })
+}
+
+fn utf16_len(text: &str) -> usize {
+    text.chars().map(char::len_utf16).sum()
+}
+
+/// The byte offset in `line` of the (UTF-16) `character`.
+fn byte_offset(line: &str, character: usize) -> usize {
+    let mut units = 0;
+    for (idx, c) in line.char_indices() {
+        if units >= character {
+            return idx;
+        }
+        units += c.len_utf16();
+    }
+    line.len()
+}
+
+#[test]
+fn scripted_client() {
+    const URI: &str = "file:///buying.feature";
+    const FEATURE: &str = "Feature: buying
+
+  @fast
+  Scenario: paying
+    Given a shop
+    When I pay
+
+  Scenario Outline: many
+    Given <n> items
+
+    Examples: few
+      |n|
+      |1|
+";
+
+    let requests = [
+        json!({ "id": 1, "method": "initialize", "params": {} }),
+        json!({ "method": "initialized", "params": {} }),
+        json!({
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": URI, "text": FEATURE } },
+        }),
+        json!({ "id": 2, "method": "textDocument/documentSymbol", "params": { "textDocument": { "uri": URI } } }),
+        json!({ "id": 3, "method": "textDocument/foldingRange", "params": { "textDocument": { "uri": URI } } }),
+        json!({ "id": 4, "method": "textDocument/formatting", "params": { "textDocument": { "uri": URI } } }),
+        json!({
+            "id": 5,
+            "method": "textDocument/completion",
+            "params": { "textDocument": { "uri": URI }, "position": { "line": 5, "character": 9 } },
+        }),
+        json!({
+            "id": 6,
+            "method": "textDocument/completion",
+            "params": { "textDocument": { "uri": URI }, "position": { "line": 1, "character": 2 } },
+        }),
+        json!({
+            "method": "textDocument/didChange",
+            "params": {
+                "textDocument": { "uri": URI },
+                "contentChanges": [{ "text": "Feature: broken\n  Scenario: x\n  Scenario: y\n    Given a\n" }],
+            },
+        }),
+        json!({ "id": 7, "method": "textDocument/hover", "params": {} }),
+        json!({ "id": 8, "method": "shutdown" }),
+        json!({ "method": "exit" }),
+        json!({ "id": 9, "method": "shutdown" }),
+    ];
+
+    let mut input = Vec::new();
+    for request in &requests {
+        if request["id"] == 7 {
+            // A message that is not JSON does not stop the server.
+            input.extend_from_slice(b"Content-Length: 9\r\n\r\n{invalid}");
+        }
+        write_message(&mut input, request).unwrap();
+    }
+
+    let mut output = Vec::new();
+    serve(&input[..], &mut output).unwrap();
+
+    let mut output = &output[..];
+    let mut responses = Vec::new();
+    while let Some(response) = read_message(&mut output).unwrap() {
+        responses.push(response);
+    }
+
+    assert_eq!(responses.len(), 11);
+    assert_eq!(
+        responses[0]["result"]["capabilities"]["foldingRangeProvider"],
+        true
+    );
+
+    // Opening the document publishes its diagnostics, of which there are none.
+    assert_eq!(responses[1]["params"]["diagnostics"], json!([]));
+
+    let feature = &responses[2]["result"][0];
+    assert_eq!(feature["name"], "Feature: buying");
+    assert_eq!(feature["range"]["end"]["line"], 12);
+    let children: Vec<_> = feature["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| {
+            (
+                c["name"].as_str().unwrap(),
+                c["range"]["start"]["line"].as_u64().unwrap(),
+                c["range"]["end"]["line"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        children,
+        [
+            ("Scenario: paying", 3, 5),
+            ("Scenario Outline: many", 7, 12)
+        ]
+    );
+    assert_eq!(
+        feature["children"][1]["children"][0]["name"],
+        "Examples: few"
+    );
+
+    assert_eq!(
+        responses[3]["result"],
+        json!([
+            { "startLine": 0, "endLine": 12 },
+            { "startLine": 3, "endLine": 5 },
+            { "startLine": 7, "endLine": 12 },
+            { "startLine": 10, "endLine": 12 },
+        ])
+    );
+
+    let edit = &responses[4]["result"][0];
+    assert_eq!(edit["range"]["end"], json!({ "line": 13, "character": 0 }));
+    assert_eq!(
+        edit["newText"],
+        render(&parse(URI, FEATURE).unwrap(), Style::Plain)
+    );
+
+    let labels = |response: &Value| -> Vec<String> {
+        response["result"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|item| item["label"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(labels(&responses[5]), ["<n> items", "I pay", "a shop"]);
+    assert_eq!(
+        responses[5]["result"][0]["textEdit"]["range"]["start"],
+        json!({ "line": 5, "character": 9 })
+    );
+    assert_eq!(labels(&responses[6]).len(), KEYWORDS.len());
+
+    let diagnostics = &responses[7]["params"]["diagnostics"];
+    assert_eq!(diagnostics[0]["severity"], 1);
+    assert_eq!(
+        diagnostics[0]["range"]["start"],
+        json!({ "line": 1, "character": 2 })
+    );
+
+    assert_eq!(responses[8]["id"], Value::Null);
+    assert_eq!(responses[8]["error"]["code"], PARSE_ERROR);
+    assert_eq!(responses[9]["error"]["code"], METHOD_NOT_FOUND);
+    assert_eq!(
+        responses[10],
+        json!({ "jsonrpc": "2.0", "id": 8, "result": null })
+    );
+}
+
+#[test]
+fn source_examples() {
+    let dir = std::env::temp_dir().join(format!("gherkin lsp-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("prices.csv"), "n\n1\n").unwrap();
+
+    let uri = format!("file://{}/prices.feature", dir.display()).replace(' ', "%20");
+    let feature = "Feature: prices
+  Scenario Outline: many
+    Given <n> items
+
+    @source(prices.csv)
+    Examples:
+";
+
+    let requests = [
+        json!({
+            "method": "textDocument/didOpen",
+            "params": { "textDocument": { "uri": uri, "text": feature } },
+        }),
+        json!({ "id": 1, "method": "textDocument/documentSymbol", "params": { "textDocument": { "uri": uri } } }),
+    ];
+
+    let mut input = Vec::new();
+    for request in &requests {
+        write_message(&mut input, request).unwrap();
+    }
+
+    let mut output = Vec::new();
+    serve(&input[..], &mut output).unwrap();
+
+    let mut output = &output[..];
+    let diagnostics = read_message(&mut output).unwrap().unwrap();
+    assert_eq!(diagnostics["params"]["diagnostics"], json!([]));
+    let symbols = read_message(&mut output).unwrap().unwrap();
+    assert_eq!(
+        symbols["result"][0]["children"][0]["name"],
+        "Scenario Outline: many"
+    );
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn formatting_with_comments() {
+    let text = "Feature: f\n  # Why this matters.\n  Scenario: s\n      Given x\n";
+    let feature = parse("file:///f.feature", text).unwrap();
+    assert_eq!(formatting(text, &feature), json!([]));
+
+    let text = text.replace("  # Why this matters.\n", "");
+    let feature = parse("file:///f.feature", &text).unwrap();
+    assert_eq!(
+        formatting(&text, &feature)[0]["newText"],
+        render(&feature, Style::Plain)
+    );
+}
@@ -0,0 +1,408 @@
+//! Reparsing of feature files as they are edited, for editors and watch
+//! mode runners.
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::ops::Range;
+
+use super::{ParseError, ParserInner, ParserOptions};
+use crate::{Feature, KeywordType, Location, Scenario, ScenarioOutline, Step};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum NodeKind {
+    Scenario,
+    ScenarioOutline,
+}
+
+/// The (0-based) lines of a scenario or scenario outline. Parsing it starts
+/// at `start`, before the empty lines, comments and tags leading up to it,
+/// and stops at `end`, which it has peeked at.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Span {
+    pub(super) kind: NodeKind,
+    pub(super) start: usize,
+    pub(super) end: usize,
+}
+
+/// A replacement of the text in a byte range of a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub range: Range<usize>,
+    pub text: String,
+}
+
+impl TextEdit {
+    pub fn new(range: Range<usize>, text: impl Into<String>) -> Self {
+        Self {
+            range,
+            text: text.into(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Parsed {
+    feature: Feature,
+    /// The line after the feature keyword, description and background.
+    header_end: usize,
+    spans: Vec<Span>,
+}
+
+/// A feature file that is reparsed incrementally as it is edited.
+///
+/// An edit only reparses the scenarios and scenario outlines whose lines it
+/// touches. The others are reused, with their locations moved, so the
+/// feature is always equal to that of a full reparse. Edits to the feature
+/// keyword, its description or the background reparse the whole file.
+///
+/// ```
+/// use gherkin::{Document, ParserOptions, TextEdit};
+///
+/// let text = "Feature: buying
+///   Scenario: one
+///     Given a banana
+///
+///   Scenario: two
+///     Given an apple
+/// ";
+/// let mut document = Document::new(text, ParserOptions::strict());
+///
+/// let offset = text.find("an apple").unwrap();
+/// let feature = document
+///     .edit(TextEdit::new(offset..offset + 8, "a pear"))
+///     .unwrap();
+///
+/// assert_eq!(feature.scenarios[1].steps[0].description, "a pear");
+/// assert_eq!(document.reused(), 1);
+/// ```
+pub struct Document<'a> {
+    text: String,
+    options: ParserOptions<'a>,
+    parsed: Result<Parsed, ParseError>,
+    reused: usize,
+}
+
+impl<'a> Document<'a> {
+    pub fn new(text: impl Into<String>, options: ParserOptions<'a>) -> Self {
+        let text = text.into();
+        let parsed = parse(&text, options);
+
+        Self {
+            text,
+            options,
+            parsed,
+            reused: 0,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The feature, or the error that parsing the current text produced.
+    pub fn feature(&self) -> Result<&Feature, &ParseError> {
+        self.parsed.as_ref().map(|parsed| &parsed.feature)
+    }
+
+    /// The amount of scenarios and scenario outlines that the last edit
+    /// did not reparse.
+    pub fn reused(&self) -> usize {
+        self.reused
+    }
+
+    /// Apply `edit` to the text and reparse what it affects.
+    ///
+    /// # Panics
+    ///
+    /// If the range of the edit is out of bounds, or does not lie on `char`
+    /// boundaries.
+    pub fn edit(&mut self, edit: TextEdit) -> Result<&Feature, &ParseError> {
+        let lines = Edited {
+            first: self.text[..edit.range.start].matches('\n').count(),
+            last: self.text[..edit.range.end].matches('\n').count(),
+            added: edit.text.matches('\n').count(),
+        };
+        self.text.replace_range(edit.range, &edit.text);
+
+        let result = match &mut self.parsed {
+            Ok(old) if old.header_end < lines.first => {
+                reparse(&self.text, self.options, old, lines)
+            }
+            _ => parse(&self.text, self.options).map(|parsed| (parsed, 0)),
+        };
+        self.reused = 0;
+        self.parsed = result.map(|(parsed, reused)| {
+            self.reused = reused;
+            parsed
+        });
+
+        self.feature()
+    }
+}
+
+/// The (0-based) lines an edit replaced, and the amount of lines it added.
+#[derive(Clone, Copy)]
+struct Edited {
+    first: usize,
+    last: usize,
+    added: usize,
+}
+
+impl Edited {
+    /// The new number of a line after the edit.
+    fn line(self, line: usize) -> usize {
+        line - (self.last - self.first) + self.added
+    }
+
+    fn shift(self, location: &mut Location) {
+        location.line = self.line(location.line);
+    }
+
+    fn shift_steps(self, steps: &mut [Step]) {
+        steps.iter_mut().for_each(|s| self.shift(&mut s.location));
+    }
+}
+
+fn parse(text: &str, options: ParserOptions) -> Result<Parsed, ParseError> {
+    let mut parser = ParserInner::new(text, options);
+    let mut feature = parser.match_header()?;
+    let header_end = parser.current_line;
+
+    let mut spans = Vec::new();
+    parser.match_nodes(&mut feature, &mut spans, |_| false)?;
+
+    Ok(Parsed {
+        feature,
+        header_end,
+        spans,
+    })
+}
+
+/// The amount of scenarios and of scenario outlines in `spans`.
+fn count(spans: &[Span]) -> (usize, usize) {
+    let scenarios = spans
+        .iter()
+        .filter(|s| s.kind == NodeKind::Scenario)
+        .count();
+    (scenarios, spans.len() - scenarios)
+}
+
+/// Reparse the nodes from before the edit until parsing arrives at the start
+/// of a node after it, from which on the old nodes are reused. Returns the
+/// new parse, and the amount of reused nodes.
+fn reparse(
+    text: &str,
+    options: ParserOptions,
+    old: &mut Parsed,
+    edited: Edited,
+) -> Result<(Parsed, usize), ParseError> {
+    // Nodes before the edit that did not peek at its lines, and the first
+    // node after it.
+    let leading = old
+        .spans
+        .iter()
+        .take_while(|s| s.end < edited.first)
+        .count();
+    let trailing = old
+        .spans
+        .iter()
+        .position(|s| s.start > edited.last)
+        .unwrap_or(old.spans.len());
+
+    let (leading_scenarios, leading_outlines) = count(&old.spans[..leading]);
+    let (trailing_scenarios, trailing_outlines) = count(&old.spans[..trailing]);
+    let mut later_scenarios = old.feature.scenarios.split_off(trailing_scenarios);
+    let mut later_outlines = old.feature.scenario_outlines.split_off(trailing_outlines);
+    old.feature.scenarios.truncate(leading_scenarios);
+    old.feature.scenario_outlines.truncate(leading_outlines);
+
+    let mut parser = ParserInner::new(text, options);
+    parser.current_line = old.spans[..leading]
+        .last()
+        .map_or(old.header_end, |s| s.end);
+    parser.feature_name = Some(
+        old.feature
+            .name
+            .clone()
+            .unwrap_or("Unnamed feature".to_string()),
+    );
+    parser.background_keyword_type = old
+        .feature
+        .background
+        .as_ref()
+        .and_then(|b| b.steps.last())
+        .map_or(KeywordType::Unknown, |s| s.keyword_type);
+
+    let mut feature = Feature {
+        tags: core::mem::take(&mut old.feature.tags),
+        name: old.feature.name.take(),
+        description: old.feature.description.take(),
+        background: old.feature.background.take(),
+        scenarios: core::mem::take(&mut old.feature.scenarios),
+        scenario_outlines: core::mem::take(&mut old.feature.scenario_outlines),
+        location: old.feature.location,
+    };
+    let mut spans = old.spans[..leading].to_vec();
+
+    let candidates = &old.spans[trailing..];
+    let mut landed = None;
+    parser.match_nodes(&mut feature, &mut spans, |line| {
+        landed = candidates.iter().position(|s| edited.line(s.start) == line);
+        landed.is_some()
+    })?;
+
+    let mut reused = leading;
+    if let Some(landed) = landed {
+        let (skipped_scenarios, skipped_outlines) = count(&candidates[..landed]);
+        later_scenarios.drain(..skipped_scenarios);
+        later_outlines.drain(..skipped_outlines);
+
+        for scenario in &mut later_scenarios {
+            shift_scenario(edited, scenario);
+        }
+        for outline in &mut later_outlines {
+            shift_outline(edited, outline);
+        }
+        spans.extend(candidates[landed..].iter().map(|s| Span {
+            kind: s.kind,
+            start: edited.line(s.start),
+            end: edited.line(s.end),
+        }));
+
+        reused += candidates.len() - landed;
+        feature.scenarios.append(&mut later_scenarios);
+        feature.scenario_outlines.append(&mut later_outlines);
+    }
+
+    Ok((
+        Parsed {
+            feature,
+            header_end: old.header_end,
+            spans,
+        },
+        reused,
+    ))
+}
+
+fn shift_scenario(edited: Edited, scenario: &mut Scenario) {
+    edited.shift(&mut scenario.location);
+    edited.shift_steps(&mut scenario.steps);
+}
+
+fn shift_outline(edited: Edited, outline: &mut ScenarioOutline) {
+    edited.shift(&mut outline.location);
+    edited.shift_steps(&mut outline.steps);
+    for examples in &mut outline.scenarios {
+        edited.shift(&mut examples.location);
+        examples
+            .row_locations
+            .iter_mut()
+            .for_each(|l| edited.shift(l));
+    }
+}
+
+#[test]
+fn equals_full_reparse() {
+    const FEATURE: &str = r#"@shop
+Feature: buying
+  Background:
+    Given a shop
+
+  # The simplest case.
+  Scenario: one
+    And a banana
+    When I buy it
+
+  @wip
+  Scenario Outline: many
+    When I buy <count> bananas
+      """
+      <count>
+      """
+
+    Examples:
+      | count |
+      | 1     |
+      | 2     |
+
+  Scenario: table
+    Given the prices
+      | item   | price |
+      | banana | 1     |
+    Then I pay 1
+"#;
+
+    let check = |document: &Document, expected_reused: Option<usize>| {
+        let full = document.options.parse(document.text());
+        assert_eq!(document.feature(), full.as_ref(), "{}", document.text());
+        if let Some(reused) = expected_reused {
+            assert_eq!(document.reused(), reused, "{}", document.text());
+        }
+    };
+
+    // Every line deleted, duplicated, or prefixed with an empty line, a
+    // step, a tag or a doc string delimiter.
+    let starts: Vec<_> = core::iter::once(0)
+        .chain(FEATURE.match_indices('\n').map(|(idx, _)| idx + 1))
+        .collect();
+    for lines in starts.windows(2) {
+        let line = &FEATURE[lines[0]..lines[1]];
+        let edits = [
+            TextEdit::new(lines[0]..lines[1], ""),
+            TextEdit::new(lines[0]..lines[0], line),
+            TextEdit::new(lines[0]..lines[0], "\n"),
+            TextEdit::new(lines[0]..lines[0], "    And a pear\n"),
+            TextEdit::new(lines[0]..lines[0], "  @tag\n"),
+            TextEdit::new(lines[0]..lines[0], "      \"\"\"\n"),
+        ];
+        for options in [ParserOptions::strict(), ParserOptions::lenient()] {
+            for edit in edits.clone() {
+                let mut document = Document::new(FEATURE, options);
+                document.edit(edit).ok();
+                check(&document, None);
+            }
+        }
+    }
+
+    // Edits within a node reuse the others.
+    let mut document = Document::new(FEATURE, ParserOptions::lenient());
+    let start = FEATURE.find("I buy it").unwrap();
+    document
+        .edit(TextEdit::new(start..start + 8, "I buy\n    Then I pay"))
+        .unwrap();
+    check(&document, Some(2));
+
+    let start = document.text().find("| 2     |").unwrap();
+    document
+        .edit(TextEdit::new(start..start, "| 3     |\n      "))
+        .unwrap();
+    check(&document, Some(2));
+
+    let start = document.text().find("| banana | 1").unwrap();
+    document
+        .edit(TextEdit::new(start..start + 12, "| apple  | 2"))
+        .unwrap();
+    check(&document, Some(2));
+
+    // Errors are those of a full reparse, and editing after them reparses
+    // everything.
+    let start = document.text().find("  Scenario Outline").unwrap();
+    let edit = TextEdit::new(start..start, "    Then I see\n      \"\"\"\n");
+    document.edit(edit).unwrap_err();
+    check(&document, None);
+    document.edit(TextEdit::new(start..start + 25, "")).unwrap();
+    check(&document, Some(0));
+
+    // Edits to the header reparse everything.
+    let start = document.text().find("a shop").unwrap();
+    document
+        .edit(TextEdit::new(start..start + 6, "a market"))
+        .unwrap();
+    check(&document, Some(0));
+    assert_eq!(
+        document.feature().unwrap().scenarios[0].steps[0].keyword_type,
+        KeywordType::Context
+    );
+}
@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Keyword {
     Feature,
     Scenario,
@@ -49,13 +49,17 @@ impl Keyword {
         ]
     }
 
+    /// Parse the keyword at the start of `line`. Keywords are matched
+    /// case-insensitively, with full case folding.
+    ///
+    /// Returns the keyword, the keyword as written in `line`, the rest of
+    /// the line after the keyword (and its colon, if it has one), and
+    /// whether the line ends in a colon. If `strip_trailing_colon` is set,
+    /// that trailing colon is removed from the rest of the line.
     pub fn parse(line: &str, strip_trailing_colon: bool) -> Option<(Self, &str, &str, bool)> {
-        let lowercase = line.to_ascii_lowercase();
-
-        let (keyword, start) = Self::combinations()
+        let (keyword, start_len) = Self::combinations()
             .iter()
-            .find(|(_, pattern)| lowercase.starts_with(pattern))?;
-        let start_len = start.len();
+            .find_map(|(keyword, pattern)| Some((*keyword, folded_prefix_len(line, pattern)?)))?;
 
         let keyword_name = &line[..start_len];
         let leftover = &line[start_len..];
@@ -69,14 +73,89 @@ impl Keyword {
             leftover.trim_start()
         };
 
-        let last_is_colon = leftover.trim_end().ends_with(':');
+        let without_colon = leftover.trim_end().strip_suffix(':');
+        let last_is_colon = without_colon.is_some();
 
-        let leftover = if last_is_colon && strip_trailing_colon {
-            &leftover[..leftover.len() - 1]
-        } else {
-            leftover
+        let leftover = match without_colon {
+            Some(without_colon) if strip_trailing_colon => without_colon,
+            _ => leftover,
         };
 
-        Some((*keyword, keyword_name, leftover, last_is_colon))
+        Some((keyword, keyword_name, leftover, last_is_colon))
+    }
+}
+
+/// The full case folding of `c`: its lowercase form, except for the
+/// characters that fold differently, such as `ß`, which folds to `ss`.
+fn fold(c: char) -> impl Iterator<Item = char> {
+    let special = match c {
+        'ß' | 'ẞ' => Some("ss"),
+        'ſ' => Some("s"),
+        'ς' => Some("σ"),
+        'ﬀ' => Some("ff"),
+        'ﬁ' => Some("fi"),
+        'ﬂ' => Some("fl"),
+        'ﬃ' => Some("ffi"),
+        'ﬄ' => Some("ffl"),
+        'ﬅ' | 'ﬆ' => Some("st"),
+        _ => None,
+    };
+
+    let (special, lowercase) = match special {
+        Some(folded) => (Some(folded.chars()), None),
+        None => (None, Some(c.to_lowercase())),
+    };
+    special
+        .into_iter()
+        .flatten()
+        .chain(lowercase.into_iter().flatten())
+}
+
+/// The length in bytes of the prefix of `line` that case folds to `pattern`,
+/// if there is one.
+fn folded_prefix_len(line: &str, pattern: &str) -> Option<usize> {
+    let mut pattern = pattern.chars().peekable();
+
+    for (idx, c) in line.char_indices() {
+        for folded in fold(c) {
+            if pattern.next() != Some(folded) {
+                return None;
+            }
+        }
+
+        if pattern.peek().is_none() {
+            return Some(idx + c.len_utf8());
+        }
     }
+
+    None
+}
+
+#[test]
+fn unicode() {
+    assert_eq!(
+        Keyword::parse("SCENARIO: ı and İ", false),
+        Some((Keyword::Scenario, "SCENARIO", "ı and İ", false))
+    );
+    // The Kelvin sign and the long s fold to `k` and `s`.
+    assert_eq!(
+        Keyword::parse("Bac\u{212a}ground:", false),
+        Some((Keyword::Background, "Bac\u{212a}ground", "", false))
+    );
+    assert_eq!(
+        Keyword::parse("ſcenario: ok", false).map(|(k, name, ..)| (k, name)),
+        Some((Keyword::Scenario, "ſcenario"))
+    );
+    // A dotted capital I lowercases to two characters, so it is not an `i`.
+    assert_eq!(Keyword::parse("GİVEN a banana", false), None);
+    assert_eq!(Keyword::parse("Ünd a banana", false), None);
+
+    assert_eq!(
+        Keyword::parse("Given a price in €:\u{3000}", true),
+        Some((Keyword::Given, "Given", "a price in €", true))
+    );
+    assert_eq!(
+        Keyword::parse("When\u{a0}I pay", false),
+        Some((Keyword::When, "When", "I pay", false))
+    );
 }
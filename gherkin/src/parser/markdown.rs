@@ -0,0 +1,205 @@
+//! Markdown with Gherkin (MDG), as in `.feature.md` files.
+//!
+//! A Markdown document is translated into a Gherkin document line by line,
+//! so that the lines and columns of the translated document are those of
+//! the Markdown document:
+//!
+//! - Headings starting with `Feature:`, `Background:`, `Scenario:` and so
+//!   on, such as `## Scenario: buying`, are keyword lines.
+//! - List items starting with a step keyword, such as `* Given a shop`, are
+//!   steps.
+//! - Tables are data tables or examples. Their delimiter row is dropped.
+//! - Fenced code blocks following a step are doc strings.
+//! - Lines consisting of tags in backticks, such as `` `@slow` ``, are tags.
+//! - Text following a feature or scenario heading is its description. Any
+//!   other text is ignored.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use super::Keyword;
+
+/// The delimiter of a fenced code block.
+const FENCE: &str = "```";
+
+/// The state of the translation, which depends on the preceding lines.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Text is part of a description.
+    Description,
+    /// Directly after a step, where a code block is a doc string.
+    Step,
+    /// Inside a code block that is a doc string.
+    DocString,
+    /// Inside a code block that is ignored.
+    CodeBlock,
+    Other,
+}
+
+/// Translate the Markdown document `markdown` into a Gherkin document with
+/// the same lines and columns.
+pub(crate) fn to_gherkin(markdown: &str) -> String {
+    let mut state = State::Other;
+    let mut output = String::with_capacity(markdown.len());
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        let indent = &line[..line.len() - line.trim_start().len()];
+
+        let (translated, next_state) = match state {
+            State::DocString | State::CodeBlock if trimmed.starts_with(FENCE) => {
+                let translated = (state == State::DocString).then(|| delimiter(indent));
+                (translated, State::Other)
+            }
+            State::DocString => (Some(line.to_string()), state),
+            State::CodeBlock => (None, state),
+            _ if trimmed.is_empty() => (None, state),
+            State::Step if trimmed.starts_with(FENCE) => {
+                (Some(delimiter(indent)), State::DocString)
+            }
+            _ if trimmed.starts_with(FENCE) => (None, State::CodeBlock),
+            _ if trimmed.starts_with('#') => heading(line),
+            _ if trimmed.starts_with(['*', '-', '+']) => match list_item(line) {
+                Some(step) => (Some(step), State::Step),
+                None => (None, State::Other),
+            },
+            _ if trimmed.starts_with('|') => (
+                Some(line)
+                    .filter(|l| !is_delimiter_row(l))
+                    .map(String::from),
+                state,
+            ),
+            _ if is_tag_line(trimmed) => (
+                Some(line.replace('`', " ").trim_end().to_string()),
+                State::Other,
+            ),
+            State::Description => (Some(line.to_string()), state),
+            _ => (None, State::Other),
+        };
+
+        if let Some(translated) = translated {
+            output.push_str(&translated);
+        }
+        output.push('\n');
+        state = next_state;
+    }
+
+    output
+}
+
+/// A doc string delimiter replacing a fence. The media type following an
+/// opening fence is dropped.
+fn delimiter(indent: &str) -> String {
+    format!("{indent}\"\"\"")
+}
+
+/// Translate a heading, which is a keyword line if it starts with a keyword
+/// that is followed by a colon.
+fn heading(line: &str) -> (Option<String>, State) {
+    let text = line.trim_start().trim_start_matches('#');
+    let Some((keyword, ..)) = Keyword::parse(text.trim_start(), false) else {
+        return (None, State::Other);
+    };
+
+    if !keyword.has_colon() {
+        return (None, State::Other);
+    }
+
+    let state = match keyword {
+        Keyword::Feature | Keyword::Scenario | Keyword::ScenarioOutline => State::Description,
+        _ => State::Other,
+    };
+
+    (Some(replace_marker(line, text)), state)
+}
+
+/// Translate a list item, which is a step if it starts with a step keyword.
+fn list_item(line: &str) -> Option<String> {
+    let text = &line.trim_start()[1..];
+    if !text.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    let (keyword, ..) = Keyword::parse(text.trim_start(), false)?;
+    (!keyword.has_colon()).then(|| replace_marker(line, text))
+}
+
+/// Replace the Markdown marker in front of `text`, which is a suffix of
+/// `line`, with spaces.
+fn replace_marker(line: &str, text: &str) -> String {
+    let marker_end = line.len() - text.len();
+    let indent = line.len() - line.trim_start().len();
+    format!(
+        "{}{}{text}",
+        &line[..indent],
+        " ".repeat(marker_end - indent)
+    )
+}
+
+fn is_delimiter_row(line: &str) -> bool {
+    line.trim()
+        .chars()
+        .all(|c| matches!(c, '|' | '-' | ':' | ' ' | '\t'))
+        && line.contains('-')
+}
+
+/// Whether `trimmed` consists of tags in backticks, such as `` `@a` `@b` ``.
+fn is_tag_line(trimmed: &str) -> bool {
+    trimmed.split_whitespace().all(|word| {
+        word.strip_prefix('`')
+            .and_then(|w| w.strip_suffix('`'))
+            .is_some_and(|tag| tag.starts_with('@') && tag.len() > 1)
+    })
+}
+
+#[test]
+fn translation() {
+    let markdown = "# Feature: buying
+Some *description*.
+
+`@fast` `@shop`
+## Scenario: paying
+The scenario description.
+
+* Given the prices
+  | item   | price |
+  | ------ | ----: |
+  | banana | 1     |
+- When I pay
+- Then I receive
+  ```json
+  {\"paid\": true}
+  ```
+
+```
+not a doc string
+```
+";
+
+    assert_eq!(
+        to_gherkin(markdown),
+        "  Feature: buying
+Some *description*.
+
+ @fast   @shop
+   Scenario: paying
+The scenario description.
+
+  Given the prices
+  | item   | price |
+
+  | banana | 1     |
+  When I pay
+  Then I receive
+  \"\"\"
+  {\"paid\": true}
+  \"\"\"
+
+
+
+
+"
+    );
+}
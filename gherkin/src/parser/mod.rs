@@ -1,107 +1,192 @@
-use crate::scenario_outline::TaggedScenarios;
+use crate::{examples_loader::examples_source, scenario_outline::TaggedScenarios};
 
 use super::*;
 
+mod incremental;
+pub use incremental::{Document, TextEdit};
+use incremental::{NodeKind, Span};
+
 mod keyword;
-use keyword::Keyword;
+pub use keyword::Keyword;
+
+mod markdown;
+
+mod options;
+pub use options::ParserOptions;
 
 #[cfg(test)]
 mod test;
 
-use std::{collections::HashSet, iter::Peekable, str::Lines};
+use alloc::{collections::BTreeSet, format, string::ToString, vec};
+
+/// The delimiter of a doc string.
+pub(crate) const DOC_STRING_DELIMITER: &str = "\"\"\"";
+
+/// A [`DOC_STRING_DELIMITER`] inside a doc string, escaped so that it does not
+/// end the doc string.
+pub(crate) const ESCAPED_DOC_STRING_DELIMITER: &str = "\\\"\\\"\\\"";
+
+/// A parsed keyword line: the keyword, the rest of the line (if any) and
+/// whether the line ended in a colon.
+type KeywordLine<'a> = (Keyword, Option<&'a str>, bool);
+
+/// An error produced while parsing a feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// The location of the line on which the error occurred.
+    pub location: Location,
+    line_text: String,
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}.\n--> {} <--", self.message, self.line_text)
+    }
+}
+
+impl core::error::Error for ParseError {}
 
 struct ParserInner<'a> {
     current_line: usize,
     text: &'a str,
-    lines: Peekable<Lines<'a>>,
+    lines: Vec<&'a str>,
     feature_name: Option<String>,
+    options: ParserOptions<'a>,
+    /// The keyword type of the last step of the background, which is
+    /// inherited by leading `And`, `But` and `*` steps of scenarios.
+    background_keyword_type: KeywordType,
 }
 
 impl<'a> Iterator for ParserInner<'a> {
     type Item = &'a str;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(line) = self.lines.next() {
-            self.current_line += 1;
-            Some(line)
-        } else {
-            None
-        }
+        let line = self.peek()?;
+        self.current_line += 1;
+        Some(line)
     }
 }
 
 impl<'a> ParserInner<'a> {
-    fn new(input: &'a str) -> Self {
+    fn new(input: &'a str, options: ParserOptions<'a>) -> Self {
         Self {
             text: input,
             current_line: 0,
-            lines: input.lines().peekable(),
+            lines: input.lines().collect(),
             feature_name: None,
+            options,
+            background_keyword_type: KeywordType::Unknown,
         }
     }
 
-    fn format_error<T>(message: &str, text: &str, line_number: usize) -> Result<T, String> {
-        let line = text.lines().skip(line_number).next().unwrap();
-        Err(format!("{message}.\n--> {line} <--"))
+    /// An error on the (0-based) line `line_number`. Errors at the end of
+    /// input are reported on the last line.
+    fn format_error<T>(message: &str, text: &str, line_number: usize) -> Result<T, ParseError> {
+        let line_count = text.lines().count();
+        let line_number = line_number.min(line_count.saturating_sub(1));
+        let line = text.lines().nth(line_number);
+
+        Err(ParseError {
+            message: message.to_string(),
+            location: Location::of_line(line_number + 1, line.unwrap_or_default()),
+            line_text: line.unwrap_or("<end of input>").to_string(),
+        })
+    }
+
+    /// The location of the next line.
+    fn peek_location(&mut self) -> Location {
+        let Some(line) = self.peek() else {
+            return Location::of_line(self.current_line + 1, "");
+        };
+
+        // The line may be the rest of a line after its tags, so the column
+        // is determined from its position in the input.
+        let start = line.as_ptr() as usize - self.text.as_ptr() as usize;
+        let line_start = self.text[..start].rfind('\n').map_or(0, |idx| idx + 1);
+        let indent = line.len() - line.trim_start().len();
+        let column = self.text[line_start..start + indent].chars().count() + 1;
+        Location::new(self.current_line + 1, column)
+    }
+
+    /// The next line, without consuming it.
+    fn peek(&self) -> Option<&'a str> {
+        self.lines.get(self.current_line).copied()
     }
 
-    fn make_error<T>(&mut self, message: &str) -> Result<T, String> {
+    /// An error on the next line.
+    fn make_error<T>(&mut self, message: &str) -> Result<T, ParseError> {
         Self::format_error(message, self.text, self.current_line)
     }
 
+    /// An error on the (0-based) line `line`, such as a line that was
+    /// already consumed.
+    fn error_at<T>(&self, message: &str, line: usize) -> Result<T, ParseError> {
+        Self::format_error(message, self.text, line)
+    }
+
     fn take_empty_or_comment(&mut self) {
-        loop {
-            if let Some(line) = self.lines.peek() {
-                let trimmed = line.trim_start();
-                if !trimmed.starts_with('#') && !trimmed.trim_end().is_empty() {
-                    break;
-                } else {
-                    self.next();
-                }
-            } else {
+        while let Some(line) = self.peek() {
+            let trimmed = line.trim_start();
+            if !trimmed.starts_with('#') && !trimmed.trim_end().is_empty() {
                 break;
+            } else {
+                self.next();
             }
         }
     }
 
-    fn try_tags(&mut self) -> Result<Vec<String>, String> {
+    /// Read the tags on the following tag lines. Tags are separated by
+    /// whitespace, and may be followed by a comment.
+    fn try_tags(&mut self) -> Result<Vec<String>, ParseError> {
         let mut tags = Vec::new();
+        let first_line = self.current_line;
 
-        let line = if let Some(line) = self.lines.peek() {
-            line
-        } else {
-            return Ok(tags);
-        };
-
-        let trimmed = line.trim();
+        while let Some(line) = self.peek() {
+            let mut rest = line.trim();
+            if !rest.starts_with('@') {
+                break;
+            }
 
-        if !trimmed.starts_with('@') {
-            return Ok(tags);
-        }
+            while !rest.is_empty() && !rest.starts_with('#') {
+                let tag = &rest[..rest.find(char::is_whitespace).unwrap_or(rest.len())];
+                if !tag.starts_with('@') || tag.len() == 1 {
+                    if self.options.allow_tags_on_keyword_line {
+                        break;
+                    }
+                    return self
+                        .make_error(&format!("Invalid tag {tag} (does not start with '@')"));
+                }
+                tags.push(String::from(&tag[1..]));
+                rest = rest[tag.len()..].trim_start();
+            }
 
-        for tag in trimmed.split(' ') {
-            let trimmed = tag.trim();
-            if !trimmed.starts_with('@') {
-                return self
-                    .make_error(&format!("Invalid tag {trimmed} (does not start with '@')"));
+            if rest.is_empty() || rest.starts_with('#') {
+                self.next();
+                self.take_empty_or_comment();
+            } else {
+                // The rest of the line is the keyword line the tags apply to.
+                self.lines[self.current_line] = rest;
+                break;
             }
-            tags.push(String::from(&trimmed[1..]));
         }
 
-        self.next();
-
-        self.take_empty_or_comment();
-
-        if self.lines.peek().is_none() {
-            return self.make_error("Standalone tags are not allowed");
+        if !tags.is_empty() && self.peek().is_none() && !self.options.allow_standalone_tags {
+            return self.error_at("Standalone tags are not allowed", first_line);
         }
 
         Ok(tags)
     }
 
-    fn match_steps(&mut self, in_keyword: Keyword) -> Result<Vec<Step>, String> {
+    /// Parse the steps of the element with the keyword `in_keyword` on the
+    /// (0-based) line `keyword_line`.
+    fn match_steps(
+        &mut self,
+        in_keyword: Keyword,
+        keyword_line: usize,
+    ) -> Result<Vec<Step>, ParseError> {
         let mut steps = Vec::new();
-        let mut lines = Vec::new();
+        let mut keyword_type = self.background_keyword_type;
 
         loop {
             self.take_empty_or_comment();
@@ -109,11 +194,13 @@ impl<'a> ParserInner<'a> {
             let next_kw = self.peek_kw_line(true);
 
             let (kw, description, _) = match (steps.is_empty(), next_kw) {
+                (true, Err(_) | Ok(None)) if self.options.allow_empty_steps => break,
                 (true, Err(_)) => {
                     return self.make_error("Expected step keyword, but got invalid keyword line")
                 }
                 (true, Ok(None)) => {
-                    return self.make_error("Expected step keyword, but got end of input")
+                    return self
+                        .error_at("Expected step keyword, but got end of input", keyword_line)
                 }
                 (false, Err(_)) | (false, Ok(None)) => break,
                 (_, Ok(Some((kw, desc, colon)))) => (kw, desc.map(String::from), colon),
@@ -129,44 +216,53 @@ impl<'a> ParserInner<'a> {
                 _ => break,
             };
 
+            let location = self.peek_location();
             self.next();
 
             let description = if let Some(description) = description {
                 description.to_string()
             } else {
-                return self.make_error(&format!("{kw:?} step without description."));
+                return self.error_at(
+                    &format!("{kw:?} step without description"),
+                    self.current_line - 1,
+                );
             };
 
             let step_data = if let Some(table) = self.try_datatable()? {
                 Some(StepData::DataTable(table))
-            } else if let Some(docstring) = self.try_docstring()? {
-                Some(StepData::DocString(docstring))
             } else {
-                None
+                self.try_docstring()?.map(StepData::DocString)
             };
 
-            steps.push(Step::new(step_type, description, step_data));
-            lines.push(self.current_line);
+            let mut step = Step::new(step_type, description, step_data);
+            if step.keyword_type == KeywordType::Unknown {
+                step.keyword_type = keyword_type;
+            }
+            keyword_type = step.keyword_type;
+            step.location = location;
+            steps.push(step);
         }
 
-        if steps.is_empty() {
-            return self.make_error(&format!("`{in_keyword:?} Must have at least 1 step"));
+        if steps.is_empty() && !self.options.allow_empty_steps {
+            return self.error_at(
+                &format!("`{in_keyword:?}` must have at least 1 step"),
+                keyword_line,
+            );
         }
 
         // Find duplicated steps (according to gherkin spec)
         #[cfg(feature = "step-duplicate-check")]
         {
-            let mut step_set = HashSet::new();
-            if let Some((_, description)) = steps.iter().enumerate().find_map(|(idx, s)| {
-                if !step_set.insert(s.description.as_str()) {
-                    Some((lines[idx], s.description.as_str()))
-                } else {
-                    None
-                }
-            }) {
+            let mut step_set = BTreeSet::new();
+            if let Some(step) = steps
+                .iter()
+                .find(|s| !step_set.insert(s.description.as_str()))
+            {
                 log::warn!(
-                    "Duplicate step definition '{description}' in feature {}",
-                    self.feature_name.as_ref().unwrap()
+                    "Duplicate step definition '{}' on line {} in feature {}",
+                    step.description,
+                    step.location.line,
+                    self.feature_name.as_deref().unwrap_or_default()
                 );
             }
         }
@@ -174,13 +270,10 @@ impl<'a> ParserInner<'a> {
         Ok(steps)
     }
 
-    fn peek_kw_line(
-        &mut self,
-        strip_colon: bool,
-    ) -> Result<Option<(Keyword, Option<&str>, bool)>, String> {
+    fn peek_kw_line(&mut self, strip_colon: bool) -> Result<Option<KeywordLine<'a>>, ParseError> {
         self.take_empty_or_comment();
 
-        let kw_line = if let Some(line) = self.lines.peek() {
+        let kw_line = if let Some(line) = self.peek() {
             line
         } else {
             return Ok(None);
@@ -206,7 +299,7 @@ impl<'a> ParserInner<'a> {
         &mut self,
         wanted: Keyword,
         strip_colon: bool,
-    ) -> Result<(Keyword, Option<&'a str>, bool), String> {
+    ) -> Result<KeywordLine<'a>, ParseError> {
         let kw_line = if let Some(keyword_line) = self.next().map(str::trim_start) {
             keyword_line
         } else {
@@ -217,8 +310,10 @@ impl<'a> ParserInner<'a> {
             Keyword::parse(kw_line, strip_colon)
         {
             if keyword != wanted {
-                return self
-                    .make_error(&format!("Expected keyword `{wanted:?}`, got `{keyword:?}`"));
+                return self.error_at(
+                    &format!("Expected keyword `{wanted:?}`, got `{keyword:?}`"),
+                    self.current_line - 1,
+                );
             }
 
             let rest_of_str = if rest_of_str.is_empty() {
@@ -229,37 +324,45 @@ impl<'a> ParserInner<'a> {
 
             Ok((keyword, rest_of_str, has_trailing_colon))
         } else {
-            self.make_error(&format!("Unknown keyword {kw_line}"))
+            self.error_at(&format!("Unknown keyword {kw_line}"), self.current_line - 1)
         }
     }
 
-    fn try_datatable(&mut self) -> Result<Option<DataTable>, String> {
-        fn row_iter<'a>(row: &'a str) -> impl Iterator<Item = &'a str> {
-            struct Inner<'a> {
-                iter: Peekable<std::iter::Skip<std::str::Split<'a, char>>>,
-            }
-
-            impl<'a> Iterator for Inner<'a> {
-                type Item = &'a str;
+    fn try_datatable(&mut self) -> Result<Option<DataTable>, ParseError> {
+        Ok(self.try_located_datatable()?.map(|(table, _)| table))
+    }
 
-                fn next(&mut self) -> Option<Self::Item> {
-                    // TODO: escape and stuff
-                    let next_line = self.iter.next();
-                    if self.iter.peek().is_none() {
-                        None
-                    } else {
-                        next_line.map(str::trim)
-                    }
+    /// Parse a data table, along with the locations of its rows after the
+    /// header.
+    fn try_located_datatable(&mut self) -> Result<Option<(DataTable, Vec<Location>)>, ParseError> {
+        /// Split a table row into its cells, resolving the `\|`, `\\` and
+        /// `\n` escapes.
+        fn row_iter(row: &str) -> impl Iterator<Item = String> {
+            let mut cells = Vec::new();
+            let mut cell = String::new();
+            let mut chars = row.chars().skip(1);
+
+            while let Some(c) = chars.next() {
+                match c {
+                    '|' => cells.push(core::mem::take(&mut cell).trim().to_string()),
+                    '\\' => match chars.next() {
+                        Some('n') => cell.push('\n'),
+                        Some(c @ ('|' | '\\')) => cell.push(c),
+                        Some(c) => {
+                            cell.push('\\');
+                            cell.push(c);
+                        }
+                        None => cell.push('\\'),
+                    },
+                    c => cell.push(c),
                 }
             }
 
-            Inner {
-                iter: row.split('|').skip(1).peekable(),
-            }
+            cells.into_iter()
         }
 
         self.take_empty_or_comment();
-        let first_line = if let Some(line) = self.lines.peek() {
+        let first_line = if let Some(line) = self.peek() {
             line.trim()
         } else {
             return Ok(None);
@@ -269,90 +372,99 @@ impl<'a> ParserInner<'a> {
             return Ok(None);
         }
 
-        let header = row_iter(first_line).map(String::from).collect();
+        let header = row_iter(first_line).collect();
 
         self.next();
 
         let mut table = DataTable::new(header);
+        let mut row_locations = Vec::new();
 
         loop {
             self.take_empty_or_comment();
-            if let Some(next_line) = self.lines.peek() {
-                let next_line = next_line.trim();
-                if next_line.starts_with('|') && next_line.ends_with('|') {
-                    let row: Vec<_> = row_iter(next_line).map(String::from).collect();
-                    let row_len = row.len();
-                    if table.add_row(row).is_err() {
-                        return self.make_error(&format!(
-                            "Invalid column count in datatable. Expected {}, got {row_len}",
-                            table.header().len(),
-                        ));
-                    }
-                    self.next();
-                } else {
-                    break;
+            let Some(next_line) = self.peek() else {
+                break;
+            };
+
+            let next_line = next_line.trim();
+            if next_line.starts_with('|') && next_line.ends_with('|') {
+                let row: Vec<_> = row_iter(next_line).collect();
+                if let Err(e) = table.add_row(row) {
+                    return self.make_error(&e.to_string());
                 }
+                row_locations.push(self.peek_location());
+                self.next();
             } else {
                 break;
             }
         }
 
-        Ok(Some(table))
+        Ok(Some((table, row_locations)))
     }
 
-    fn try_background(&mut self) -> Result<Vec<Step>, String> {
-        if let Ok(Some((Keyword::Background, _, _))) = self.peek_kw_line(true) {
-            self.next();
-            let steps = self.match_steps(Keyword::Background)?;
-            Ok(steps)
+    fn try_background(&mut self) -> Result<Option<Background>, ParseError> {
+        let name = if let Ok(Some((Keyword::Background, name, _))) = self.peek_kw_line(false) {
+            name.map(String::from)
         } else {
-            return Ok(Vec::new());
+            return Ok(None);
+        };
+
+        let location = self.peek_location();
+        self.next();
+
+        let description = self.try_freeform_text()?;
+
+        let steps = self.match_steps(Keyword::Background, location.line - 1)?;
+        if let Some(last) = steps.last() {
+            self.background_keyword_type = last.keyword_type;
         }
+
+        Ok(Some(Background {
+            name,
+            description,
+            steps,
+            location,
+        }))
     }
 
-    fn try_freeform_text(&mut self) -> Result<Option<String>, String> {
+    fn try_freeform_text(&mut self) -> Result<Option<String>, ParseError> {
         self.take_empty_or_comment();
 
-        if self.lines.peek().is_none() {
+        if self.peek().is_none() {
             return Ok(None);
         };
 
         let mut string = String::new();
-        let mut indent: Option<String> = None;
-        loop {
-            if let Some(next_line) = self.lines.peek() {
-                let trimmed = next_line.trim();
+        let mut indent: Option<&str> = None;
+        while let Some(next_line) = self.peek() {
+            let trimmed = next_line.trim();
 
-                if trimmed.is_empty() {
-                    self.next();
-                    continue;
-                }
+            if trimmed.is_empty() {
+                self.next();
+                continue;
+            }
 
-                if let Some((_, _, _, _)) = Keyword::parse(trimmed, false) {
-                    break;
-                }
+            // A keyword, tag or table line ends the freeform text.
+            if trimmed.starts_with(['@', '|']) || Keyword::parse(trimmed, false).is_some() {
+                break;
+            }
 
-                if let Some(indent) = &indent {
-                    if !next_line.starts_with(indent) {
-                        return self.make_error("Inconsistent indentation in freeform text");
-                    }
-                    string.push_str(&next_line[indent.len()..]);
-                    string.push('\n');
+            if let Some(indent) = indent {
+                if let Some(line) = next_line.strip_prefix(indent) {
+                    string.push_str(line);
+                } else if self.options.allow_description_indentation_drift {
+                    string.push_str(next_line.trim_start());
                 } else {
-                    let indent_value: String = next_line
-                        .chars()
-                        .take_while(|c| c.is_ascii_whitespace())
-                        .collect();
-                    let indent_len = indent_value.len();
-                    indent = Some(indent_value);
-                    string.push_str(&next_line[indent_len..]);
-                    string.push('\n');
+                    return self.make_error("Inconsistent indentation in freeform text");
                 }
-
-                self.next();
+                string.push('\n');
             } else {
-                break;
+                let rest = next_line.trim_start();
+                indent = Some(&next_line[..next_line.len() - rest.len()]);
+                string.push_str(rest);
+                string.push('\n');
             }
+
+            self.next();
         }
 
         let trimmed = string.trim_end();
@@ -364,21 +476,18 @@ impl<'a> ParserInner<'a> {
         }
     }
 
-    fn try_docstring(&mut self) -> Result<Option<String>, String> {
+    fn try_docstring(&mut self) -> Result<Option<String>, ParseError> {
         self.take_empty_or_comment();
 
-        let first = if let Some(line) = self.lines.peek() {
+        let first = if let Some(line) = self.peek() {
             line
         } else {
             return Ok(None);
         };
 
-        if first.trim() == "\"\"\"" {
-            let indent: String = first
-                .chars()
-                .take_while(|c| c.is_ascii_whitespace())
-                .collect();
-            let indent_len = indent.len();
+        if first.trim() == DOC_STRING_DELIMITER {
+            let indent = &first[..first.len() - first.trim_start().len()];
+            let first_line = self.current_line;
 
             self.next();
 
@@ -387,19 +496,24 @@ impl<'a> ParserInner<'a> {
             loop {
                 if let Some(line) = self.next() {
                     let trimmed = line.trim();
-                    if trimmed == "\"\"\"" {
+                    if trimmed == DOC_STRING_DELIMITER {
                         return Ok(Some(string.trim().to_string()));
-                    } else if line.starts_with(&indent) {
-                        let actual_line = &line[indent_len..];
-                        string.push_str(actual_line);
+                    } else if let Some(actual_line) = line.strip_prefix(indent) {
+                        string.push_str(
+                            &actual_line
+                                .replace(ESCAPED_DOC_STRING_DELIMITER, DOC_STRING_DELIMITER),
+                        );
                         string.push('\n');
                     } else if trimmed.is_empty() {
                         string.push('\n');
                     } else {
-                        return self.make_error("Inconsistent whitespace in docstring");
+                        return self.error_at(
+                            "Inconsistent whitespace in docstring",
+                            self.current_line - 1,
+                        );
                     }
                 } else {
-                    return self.make_error("Unterminated newline");
+                    return self.error_at("Unterminated docstring", first_line);
                 }
             }
         } else {
@@ -407,10 +521,31 @@ impl<'a> ParserInner<'a> {
         }
     }
 
-    fn try_scenario_outline(&mut self) -> Result<Option<ScenarioOutline>, String> {
-        let outline_tags = self.try_tags()?;
+    /// Load the examples of the `Examples` on the (0-based) line `line`.
+    fn load_examples(&mut self, source: &str, line: usize) -> Result<DataTable, ParseError> {
+        let Some(loader) = self.options.examples_loader else {
+            return self.error_at(
+                &format!(
+                    "`Examples` refer to source `{source}`, but no examples loader is available"
+                ),
+                line,
+            );
+        };
 
-        self.take_empty_or_comment();
+        match loader.load(source) {
+            Ok(table) => Ok(table),
+            Err(e) => self.error_at(
+                &format!("Failed to load examples from `{source}`: {e}"),
+                line,
+            ),
+        }
+    }
+
+    fn try_scenario_outline(
+        &mut self,
+        tags: &[String],
+    ) -> Result<Option<ScenarioOutline>, ParseError> {
+        let outline_tags = tags.to_vec();
 
         let name = if let Ok(Some((Keyword::ScenarioOutline, name, _))) = self.peek_kw_line(false) {
             name.map(String::from)
@@ -418,14 +553,16 @@ impl<'a> ParserInner<'a> {
             return Ok(None);
         };
 
+        let location = self.peek_location();
         self.next();
 
         let description = self.try_freeform_text()?;
 
-        let steps = self.match_steps(Keyword::ScenarioOutline)?;
+        let outline_line = location.line - 1;
+        let steps = self.match_steps(Keyword::ScenarioOutline, outline_line)?;
 
         let mut scenarios = Vec::new();
-        let mut first_placeholders: Option<HashSet<String>> = None;
+        let mut first_placeholders: Option<BTreeSet<String>> = None;
 
         loop {
             self.take_empty_or_comment();
@@ -434,41 +571,78 @@ impl<'a> ParserInner<'a> {
 
             self.take_empty_or_comment();
 
-            let _ = match (self.peek_kw_line(false), scenarios.is_empty()) {
-                (Ok(Some((Keyword::Scenarios, _, _))), _) => {}
+            let name = match (self.peek_kw_line(false), scenarios.is_empty()) {
+                (Ok(Some((Keyword::Scenarios, name, _))), _) => name.map(String::from),
+                (Err(_) | Ok(_), true) if self.options.allow_missing_examples => break,
                 (Err(e), true) => return Err(e),
                 (Ok(_), true) => {
-                    return self.make_error(
+                    return self.error_at(
                         "Must have at least one `Scenarios` section in a `Scenario Outline`",
+                        outline_line,
                     )
                 }
                 (Err(_), false) | (Ok(_), false) => break,
             };
 
+            let location = self.peek_location();
             self.next();
 
-            self.take_empty_or_comment();
-
-            let DataTable {
-                header: placeholders,
-                rows: values,
-            } = if let Some(table) = self.try_datatable()? {
-                table
-            } else {
-                return self.make_error("Expected data table to follow `Examples`.");
+            let description = self.try_freeform_text()?;
+
+            let inline_table = self.try_located_datatable()?;
+
+            let (
+                DataTable {
+                    header: placeholders,
+                    rows: values,
+                },
+                row_locations,
+            ) = match (inline_table, examples_source(&tags)) {
+                (Some(table), None) => table,
+                (None, Some(source)) => {
+                    let table = self.load_examples(source, location.line - 1)?;
+                    let row_locations = vec![location; table.rows.len()];
+                    (table, row_locations)
+                }
+                (Some(_), Some(_)) => {
+                    return self.error_at(
+                        "`Examples` with a `@source` tag can not have an inline data table",
+                        location.line - 1,
+                    )
+                }
+                (None, None) if self.options.allow_missing_examples => {
+                    (DataTable::new(Vec::new()), Vec::new())
+                }
+                (None, None) => {
+                    return self.error_at(
+                        "Expected data table to follow `Examples`",
+                        location.line - 1,
+                    )
+                }
             };
 
             if let Some(first_placeholders) = &first_placeholders {
                 if placeholders.iter().any(|p| !first_placeholders.contains(p)) {
-                    return self.make_error(
+                    return self.error_at(
                         "Differing amount of or differently named placeholders in examples",
+                        location.line - 1,
                     );
                 }
             } else {
-                first_placeholders = Some(placeholders.clone().into_iter().collect::<HashSet<_>>());
+                first_placeholders =
+                    Some(placeholders.clone().into_iter().collect::<BTreeSet<_>>());
             }
             let placeholders = placeholders.into_iter().collect();
-            scenarios.push(TaggedScenarios::new(tags, placeholders, values)?);
+            match TaggedScenarios::new(tags, placeholders, values) {
+                Ok(examples) => scenarios.push(TaggedScenarios {
+                    name,
+                    description,
+                    location,
+                    row_locations,
+                    ..examples
+                }),
+                Err(e) => return self.error_at(&e, location.line - 1),
+            }
         }
 
         Ok(Some(ScenarioOutline {
@@ -477,13 +651,12 @@ impl<'a> ParserInner<'a> {
             description,
             steps,
             scenarios,
+            location,
         }))
     }
 
-    fn try_scenario(&mut self) -> Result<Option<Scenario>, String> {
-        let tags = self.try_tags()?;
-
-        self.take_empty_or_comment();
+    fn try_scenario(&mut self, tags: &[String]) -> Result<Option<Scenario>, ParseError> {
+        let tags = tags.to_vec();
 
         let name = if let Ok(Some((Keyword::Scenario, name, _))) = self.peek_kw_line(false) {
             name.map(String::from)
@@ -491,27 +664,37 @@ impl<'a> ParserInner<'a> {
             return Ok(None);
         };
 
+        let location = self.peek_location();
         self.next();
 
         let description = self.try_freeform_text()?;
 
-        let steps = self.match_steps(Keyword::Scenario)?;
+        let steps = self.match_steps(Keyword::Scenario, location.line - 1)?;
 
         Ok(Some(Scenario {
             tags,
             name,
             description,
             steps,
+            location,
         }))
     }
 
-    fn match_feature(mut self) -> Result<Feature, String> {
+    fn match_feature(mut self) -> Result<Feature, ParseError> {
+        let mut feature = self.match_header()?;
+        self.match_nodes(&mut feature, &mut Vec::new(), |_| false)?;
+        Ok(feature)
+    }
+
+    /// Parse everything up to the first scenario or scenario outline.
+    fn match_header(&mut self) -> Result<Feature, ParseError> {
         self.take_empty_or_comment();
 
         let feature_tags = self.try_tags()?;
 
         self.take_empty_or_comment();
 
+        let location = self.peek_location();
         let (_, rest_of_line, _) = self.match_kw_line(Keyword::Feature, false)?;
 
         let feature_name = rest_of_line.map(String::from);
@@ -527,41 +710,82 @@ impl<'a> ParserInner<'a> {
         self.take_empty_or_comment();
         let background = self.try_background()?;
 
-        let mut scenarios = Vec::new();
-        let mut scenario_outlines = Vec::new();
+        Ok(Feature {
+            tags: feature_tags,
+            name: feature_name,
+            description,
+            background,
+            scenarios: Vec::new(),
+            scenario_outlines: Vec::new(),
+            location,
+        })
+    }
 
+    /// Parse scenarios and scenario outlines into `feature`, recording their
+    /// spans, until the end of input or until `stop` returns `true` for the
+    /// line on which the next one would start.
+    fn match_nodes(
+        &mut self,
+        feature: &mut Feature,
+        spans: &mut Vec<Span>,
+        mut stop: impl FnMut(usize) -> bool,
+    ) -> Result<(), ParseError> {
         loop {
+            let start = self.current_line;
+            if stop(start) {
+                break;
+            }
+
             self.take_empty_or_comment();
 
-            if let Some(scenario) = self.try_scenario()? {
-                scenarios.push(scenario);
-            } else if let Some(scenario_outline) = self.try_scenario_outline()? {
-                scenario_outlines.push(scenario_outline);
-            } else if self.lines.peek().is_none() {
+            let tags = self.try_tags()?;
+
+            self.take_empty_or_comment();
+
+            let kind = if let Some(scenario) = self.try_scenario(&tags)? {
+                feature.scenarios.push(scenario);
+                NodeKind::Scenario
+            } else if let Some(scenario_outline) = self.try_scenario_outline(&tags)? {
+                feature.scenario_outlines.push(scenario_outline);
+                NodeKind::ScenarioOutline
+            } else if self.peek().is_none() {
                 break;
             } else {
                 return self.make_error(
-                    "Expected `Scenario`, `Example`, `Scenario Outline`, or `Scenario Template`.",
+                    "Expected `Scenario`, `Example`, `Scenario Outline`, or `Scenario Template`",
                 );
-            }
+            };
+
+            spans.push(Span {
+                kind,
+                start,
+                end: self.current_line,
+            });
         }
 
-        Ok(Feature {
-            tags: feature_tags,
-            name: feature_name,
-            description,
-            background,
-            scenarios,
-            scenario_outlines,
-        })
+        Ok(())
     }
 }
 
 pub struct Parser;
 
 impl Parser {
-    pub fn parse_feature(input: &str) -> Result<Feature, String> {
-        let inner = ParserInner::new(input);
-        inner.match_feature()
+    pub fn parse_feature(input: &str) -> Result<Feature, ParseError> {
+        ParserOptions::strict().parse(input)
+    }
+
+    /// Parse a feature written in Markdown with Gherkin (MDG), as in a
+    /// `.feature.md` file. Locations refer to the Markdown document.
+    pub fn parse_markdown_feature(input: &str) -> Result<Feature, ParseError> {
+        ParserOptions::strict().parse_markdown(input)
+    }
+
+    /// Parse a feature, resolving `Examples` tagged with `@source(<source>)`
+    /// through `loader`.
+    pub fn parse_feature_with_loader(
+        input: &str,
+        loader: &dyn ExamplesLoader,
+    ) -> Result<Feature, ParseError> {
+        ParserOptions::strict().examples_loader(loader).parse(input)
     }
 }
@@ -0,0 +1,105 @@
+use alloc::string::ToString;
+
+use super::{markdown, ParseError, ParserInner};
+use crate::{ExamplesLoader, Feature};
+
+/// Options that control how strictly feature files are parsed.
+///
+/// [`ParserOptions::strict`] (the default) rejects the constructs below,
+/// while [`ParserOptions::lenient`] accepts all of them, which helps with
+/// parsing legacy feature files:
+///
+/// ```
+/// use gherkin::ParserOptions;
+///
+/// let feature = ParserOptions::lenient()
+///     .parse("Feature: legacy\n  @wip Scenario: not written yet\n")
+///     .unwrap();
+///
+/// assert_eq!(feature.scenarios[0].tags, ["wip"]);
+/// assert!(feature.scenarios[0].steps.is_empty());
+/// ```
+#[derive(Clone, Copy, Default)]
+pub struct ParserOptions<'a> {
+    pub(crate) allow_empty_steps: bool,
+    pub(crate) allow_missing_examples: bool,
+    pub(crate) allow_standalone_tags: bool,
+    pub(crate) allow_description_indentation_drift: bool,
+    pub(crate) allow_tags_on_keyword_line: bool,
+    pub(crate) examples_loader: Option<&'a dyn ExamplesLoader>,
+}
+
+impl<'a> ParserOptions<'a> {
+    /// The default options, which reject everything that can be allowed.
+    pub fn strict() -> Self {
+        Self::default()
+    }
+
+    /// Options that allow everything that can be allowed.
+    pub fn lenient() -> Self {
+        Self {
+            allow_empty_steps: true,
+            allow_missing_examples: true,
+            allow_standalone_tags: true,
+            allow_description_indentation_drift: true,
+            allow_tags_on_keyword_line: true,
+            examples_loader: None,
+        }
+    }
+
+    /// Allow backgrounds, scenarios and scenario outlines without steps.
+    pub fn allow_empty_steps(mut self, allow: bool) -> Self {
+        self.allow_empty_steps = allow;
+        self
+    }
+
+    /// Allow scenario outlines without `Examples`, and `Examples` without a
+    /// table.
+    pub fn allow_missing_examples(mut self, allow: bool) -> Self {
+        self.allow_missing_examples = allow;
+        self
+    }
+
+    /// Allow tags at the end of a feature file, which are ignored.
+    pub fn allow_standalone_tags(mut self, allow: bool) -> Self {
+        self.allow_standalone_tags = allow;
+        self
+    }
+
+    /// Allow lines of a description to be indented less than its first
+    /// line. Such lines are added to the description without their
+    /// indentation.
+    pub fn allow_description_indentation_drift(mut self, allow: bool) -> Self {
+        self.allow_description_indentation_drift = allow;
+        self
+    }
+
+    /// Allow tags on the same line as the keyword they apply to, as in
+    /// `@wip Scenario: buying`.
+    pub fn allow_tags_on_keyword_line(mut self, allow: bool) -> Self {
+        self.allow_tags_on_keyword_line = allow;
+        self
+    }
+
+    /// Resolve `Examples` tagged with `@source(<source>)` through `loader`.
+    pub fn examples_loader(mut self, loader: &'a dyn ExamplesLoader) -> Self {
+        self.examples_loader = Some(loader);
+        self
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Feature, ParseError> {
+        ParserInner::new(input, *self).match_feature()
+    }
+
+    /// Parse a feature written in Markdown with Gherkin (MDG), as in a
+    /// `.feature.md` file. Locations refer to the Markdown document.
+    pub fn parse_markdown(&self, input: &str) -> Result<Feature, ParseError> {
+        let gherkin = markdown::to_gherkin(input);
+        self.parse(&gherkin).map_err(|mut e| {
+            if let Some(line) = input.lines().nth(e.location.line - 1) {
+                e.line_text = line.to_string();
+            }
+            e
+        })
+    }
+}
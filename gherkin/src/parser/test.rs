@@ -1,4 +1,7 @@
-use crate::{parser::ParserInner, DataTable, Parser};
+use crate::{
+    parser::ParserInner, DataTable, KeywordType, Location, Parser, ParserOptions, StepData,
+    StepType, Tag,
+};
 
 #[test]
 pub fn data_table() {
@@ -8,7 +11,7 @@ pub fn data_table() {
             | Value 21 | Value 22 | Value 23 |
         "#;
 
-    let mut inner = ParserInner::new(DATA_TABLE);
+    let mut inner = ParserInner::new(DATA_TABLE, ParserOptions::strict());
     let datatable = inner.try_datatable().unwrap().unwrap();
     let expected = DataTable::new_populated(
         vec!["Header 1".into(), "Header 2".into(), "Header 3".into()],
@@ -36,7 +39,7 @@ pub fn doc_string() {
     """
     "#;
 
-    let mut inner = ParserInner::new(DOC_STRING);
+    let mut inner = ParserInner::new(DOC_STRING, ParserOptions::strict());
     let doc_string = inner.try_docstring().unwrap().unwrap();
 
     assert_eq!(
@@ -85,5 +88,409 @@ pub fn basic() {
 
     let feature = Parser::parse_feature(KIND_OF_EMPTY).unwrap();
 
-    panic!("{feature:#?}");
+    assert_eq!(feature.name.as_deref(), Some("a feature"));
+    assert_eq!(
+        feature.description.as_deref(),
+        Some("Hehe a freeform text!\nUh oh my indentation!")
+    );
+
+    let background: Vec<_> = feature
+        .background
+        .as_ref()
+        .unwrap()
+        .steps
+        .iter()
+        .map(|s| (s.ty, s.description.as_str()))
+        .collect();
+    assert_eq!(
+        background,
+        [(StepType::Given, "some flour"), (StepType::And, "some eggs")]
+    );
+
+    assert_eq!(feature.scenarios.len(), 1);
+    let scenario = &feature.scenarios[0];
+    assert_eq!(scenario.steps.len(), 3);
+    assert_eq!(
+        scenario.steps[0].data,
+        Some(StepData::DocString("Hello there\nGeneral Kenobi".into()))
+    );
+
+    assert_eq!(feature.scenario_outlines.len(), 1);
+    let outline = &feature.scenario_outlines[0];
+    assert_eq!(outline.steps.len(), 3);
+    assert_eq!(outline.scenarios.len(), 2);
+
+    let expanded: Vec<_> = feature.scenarios().collect();
+    assert_eq!(expanded.len(), 7);
+    assert_eq!(feature.total_scenario_count(), 7);
+    assert_eq!(expanded[1].tags, ["1-to-3"]);
+    assert_eq!(expanded[1].steps[0].description, "1 biscuits");
+    assert_eq!(expanded[6].tags, ["4-to-6"]);
+    assert_eq!(expanded[6].steps[1].description, "6 cups of tea");
+}
+
+#[test]
+pub fn data_table_escapes() {
+    const DATA_TABLE: &str = r#"
+            | pipe  | backslash | newline   |
+            | a \| b | c \\ d    | e \n f    |
+        "#;
+
+    let mut inner = ParserInner::new(DATA_TABLE, ParserOptions::strict());
+    let datatable = inner.try_datatable().unwrap().unwrap();
+    let expected = DataTable::new_populated(
+        vec!["pipe".into(), "backslash".into(), "newline".into()],
+        vec![vec!["a | b".into(), "c \\ d".into(), "e \n f".into()]],
+    )
+    .unwrap();
+
+    assert_eq!(datatable, expected);
+
+    let rendered = datatable.to_string();
+    let mut inner = ParserInner::new(&rendered, ParserOptions::strict());
+    assert_eq!(inner.try_datatable().unwrap().unwrap(), expected);
+}
+
+#[test]
+pub fn examples_source() {
+    const OUTLINE: &str = r#"
+    Feature: prices
+        Scenario Outline: buying
+            Given a <item>
+            Then I pay <price>
+
+            @source(data/prices.csv)
+            Examples:
+    "#;
+
+    let loader = |source: &str| -> Result<DataTable, String> {
+        assert_eq!(source, "data/prices.csv");
+        DataTable::from_csv("item,price\nbanana,1\napple,2\n").map_err(|e| e.to_string())
+    };
+
+    let feature = Parser::parse_feature_with_loader(OUTLINE, &loader).unwrap();
+    let scenarios: Vec<_> = feature.scenarios().collect();
+    assert_eq!(scenarios.len(), 2);
+    assert_eq!(scenarios[1].steps[0].description, "a apple");
+    assert_eq!(scenarios[1].steps[1].description, "I pay 2");
+
+    assert!(Parser::parse_feature(OUTLINE).is_err());
+
+    let failing = |_: &str| -> Result<DataTable, String> { Err("nope".into()) };
+    let error = Parser::parse_feature_with_loader(OUTLINE, &failing).unwrap_err();
+    assert_eq!(
+        error.message,
+        "Failed to load examples from `data/prices.csv`: nope"
+    );
+}
+
+#[test]
+pub fn outline_tags() {
+    const FEATURE: &str = r#"
+    Feature: tags
+        @slow @db
+        Scenario Outline: tagged
+            Given <count> biscuits
+
+            Examples:
+                | count |
+                | 1     |
+    "#;
+
+    let feature = Parser::parse_feature(FEATURE).unwrap();
+
+    assert_eq!(feature.scenario_outlines[0].tags, ["slow", "db"]);
+}
+
+#[test]
+pub fn locations() {
+    const FEATURE: &str = r#"@tagged
+Feature: locations
+
+  Scenario: first
+    Given a step
+      And another step
+
+  Scenario Outline: second
+    Given <count> steps
+
+    Examples:
+      | count |
+      | 1     |
+"#;
+
+    let feature = Parser::parse_feature(FEATURE).unwrap();
+    assert_eq!(feature.location, Location::new(2, 1));
+    assert_eq!(feature.scenarios[0].location, Location::new(4, 3));
+    assert_eq!(feature.scenarios[0].steps[0].location, Location::new(5, 5));
+    assert_eq!(feature.scenarios[0].steps[1].location, Location::new(6, 7));
+    assert_eq!(feature.scenario_outlines[0].location, Location::new(8, 3));
+
+    let error =
+        Parser::parse_feature("Feature: broken\n  Scenario: x\n    Given a\n  Oops\n").unwrap_err();
+    assert_eq!(error.location, Location::new(4, 3));
+    assert_eq!(
+        error.to_string(),
+        "Expected `Scenario`, `Example`, `Scenario Outline`, or `Scenario Template`.\n-->   Oops <--"
+    );
+
+    // Errors point at the line at fault, even when it was already read.
+    let error = |input: &str| {
+        let error = Parser::parse_feature(input).unwrap_err();
+        (error.location.line, error.to_string())
+    };
+    assert_eq!(
+        error("Scenario: a\n"),
+        (
+            1,
+            "Expected keyword `Feature`, got `Scenario`.\n--> Scenario: a <--".into()
+        )
+    );
+    assert_eq!(
+        error("Feature: x\n  Scenario: x\n  Scenario: y\n    Given z\n").0,
+        2
+    );
+    assert_eq!(error("Feature: x\n  Scenario: x\n    Given\n").0, 3);
+    assert_eq!(
+        error("Feature: x\n  Scenario: x\n    Given a\n      \"\"\"\n      text\n").0,
+        4
+    );
+    assert_eq!(
+        error(
+            "Feature: x\n  Scenario Outline: o\n    Given <a>\n\n    @source(t.csv)\n    Examples:\n"
+        ),
+        (
+            6,
+            "`Examples` refer to source `t.csv`, but no examples loader is available.\n\
+             -->     Examples: <--"
+                .into()
+        )
+    );
+}
+
+#[test]
+pub fn keyword_types() {
+    const FEATURE: &str = "Feature: a feature
+  Background:
+    * a shop
+    Given a banana
+    And an apple
+
+  Scenario: buying
+    And a pear
+    When I pay
+    But only in cash
+    Then I have a receipt
+    * and a bag
+";
+
+    let feature = Parser::parse_feature(FEATURE).unwrap();
+    let types = |steps: &[crate::Step]| -> Vec<KeywordType> {
+        steps.iter().map(|s| s.keyword_type).collect()
+    };
+
+    use KeywordType::*;
+    assert_eq!(
+        types(&feature.background.unwrap().steps),
+        [Unknown, Context, Context]
+    );
+    assert_eq!(
+        types(&feature.scenarios[0].steps),
+        [Context, Action, Action, Outcome, Outcome]
+    );
+}
+
+#[test]
+pub fn markdown() {
+    const FEATURE: &str = "# Feature: buying
+
+Some description.
+
+## Background:
+
+* Given a shop
+
+`@fast`
+## Scenario Outline: paying
+
+- When I buy <count> bananas
+- Then the receipt reads
+  ```text
+  <count> bananas
+  ```
+
+### Examples:
+
+| count |
+| ----- |
+| 1     |
+| 2     |
+";
+
+    let feature = Parser::parse_markdown_feature(FEATURE).unwrap();
+    assert_eq!(feature.name.as_deref(), Some("buying"));
+    assert_eq!(feature.description.as_deref(), Some("Some description."));
+    assert_eq!(feature.background.unwrap().steps[0].description, "a shop");
+
+    let outline = &feature.scenario_outlines[0];
+    assert_eq!(outline.tags, ["fast"]);
+    assert_eq!(outline.location, Location::new(10, 4));
+    assert_eq!(outline.steps[1].location, Location::new(13, 3));
+    assert_eq!(
+        outline.steps[1].data,
+        Some(StepData::DocString("<count> bananas".into()))
+    );
+    assert_eq!(outline.scenarios[0].values(), [["1"], ["2"]]);
+
+    let error = Parser::parse_markdown_feature("# Feature: a\n\n* Given a\n").unwrap_err();
+    assert_eq!(error.location, Location::new(3, 3));
+    assert!(error.to_string().ends_with("--> * Given a <--"));
+}
+
+#[test]
+pub fn parser_options() {
+    const LEGACY: &str = "Feature: legacy
+    A description that
+  drifts to the left.
+
+  @wip Scenario: not written yet
+
+  Scenario Outline: without examples
+    Given <nothing>
+
+  @orphan
+";
+
+    let strict_errors = [
+        ParserOptions::strict().allow_description_indentation_drift(true),
+        ParserOptions::lenient().allow_description_indentation_drift(false),
+        ParserOptions::lenient().allow_tags_on_keyword_line(false),
+        ParserOptions::lenient().allow_empty_steps(false),
+        ParserOptions::lenient().allow_missing_examples(false),
+        ParserOptions::lenient().allow_standalone_tags(false),
+    ]
+    .map(|options| options.parse(LEGACY).unwrap_err().location.line);
+    assert_eq!(strict_errors, [5, 3, 5, 5, 7, 10]);
+
+    let feature = ParserOptions::lenient().parse(LEGACY).unwrap();
+    assert_eq!(
+        feature.description.as_deref(),
+        Some("A description that\ndrifts to the left.")
+    );
+
+    let scenario = &feature.scenarios[0];
+    assert_eq!(scenario.tags, ["wip"]);
+    assert_eq!(scenario.name.as_deref(), Some("not written yet"));
+    assert_eq!(scenario.location, Location::new(5, 8));
+    assert!(scenario.steps.is_empty());
+
+    assert!(feature.scenario_outlines[0].scenarios.is_empty());
+}
+
+#[test]
+pub fn tag_lines() {
+    const FEATURE: &str = "@a  @b\t@c # a comment
+@d
+
+Feature: tags
+  @e
+  # between tag lines
+  @owner(team-x) @timeout=30
+  Scenario Outline: tagged
+    Given <x>
+
+    Examples: first
+      A description.
+      | x |
+      | 1 |
+
+    @f
+    Examples: second
+      | x |
+      | 2 |
+";
+
+    let feature = Parser::parse_feature(FEATURE).unwrap();
+    assert_eq!(feature.tags, ["a", "b", "c", "d"]);
+
+    let outline = &feature.scenario_outlines[0];
+    assert_eq!(outline.tags, ["e", "owner(team-x)", "timeout=30"]);
+    assert_eq!(
+        Tag::find(&outline.tags, "timeout").and_then(|t| t.value),
+        Some("30")
+    );
+    assert_eq!(outline.location.line, 8);
+
+    assert_eq!(outline.scenarios.len(), 2);
+    assert_eq!(outline.scenarios[1].tags(), ["f"]);
+
+    assert!(Parser::parse_feature("@a b\nFeature: x\n").is_err());
+}
+
+#[test]
+pub fn background_and_examples_names() {
+    const FEATURE: &str = "Feature: prices
+  Background: a shop
+    The shop is open.
+    Given a shop
+
+  Scenario Outline: pricing
+    Given a <item>
+
+    Examples: EU prices
+      Prices in euro.
+      | item   |
+      | banana |
+";
+
+    let feature = Parser::parse_feature(FEATURE).unwrap();
+
+    let background = feature.background.unwrap();
+    assert_eq!(background.name.as_deref(), Some("a shop"));
+    assert_eq!(background.description.as_deref(), Some("The shop is open."));
+    assert_eq!(background.location, Location::new(2, 3));
+    assert_eq!(background.steps.len(), 1);
+
+    let examples = &feature.scenario_outlines[0].scenarios[0];
+    assert_eq!(examples.name(), Some("EU prices"));
+    assert_eq!(examples.description(), Some("Prices in euro."));
+    assert_eq!(examples.location(), Location::new(9, 5));
+    assert_eq!(examples.values(), [["banana"]]);
+}
+
+#[test]
+pub fn unicode() {
+    // Indented with no-break and ideographic spaces.
+    const FEATURE: &str = "Feature: Çiçek siparişi
+\u{a0}\u{a0}İstanbul'a teslimat.
+\u{a0}\u{a0}Ürün: gül
+  SCENARIO: sipariş
+    GIVEN bir müşteri
+\u{3000}\u{3000}\"\"\"
+\u{3000}\u{3000}Teşekkürler
+\u{3000}\u{3000}\u{3000}İyi günler
+\u{3000}\u{3000}\"\"\"
+";
+
+    let feature = Parser::parse_feature(FEATURE).unwrap();
+    assert_eq!(feature.name.as_deref(), Some("Çiçek siparişi"));
+    assert_eq!(
+        feature.description.as_deref(),
+        Some("İstanbul'a teslimat.\nÜrün: gül")
+    );
+
+    let step = &feature.scenarios[0].steps[0];
+    assert_eq!(step.description, "bir müşteri");
+    assert_eq!(
+        step.data,
+        Some(StepData::DocString(
+            "Teşekkürler\n\u{3000}İyi günler".into()
+        ))
+    );
+    assert_eq!(step.location, Location::new(5, 5));
+
+    // A doc string line indented less than its delimiter is an error, not a
+    // mis-sliced line.
+    let misindented = FEATURE.replace("\u{3000}\u{3000}Teşekkürler", "\u{a0}Teşekkürler");
+    assert!(Parser::parse_feature(&misindented).is_err());
 }
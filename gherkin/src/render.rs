@@ -0,0 +1,475 @@
+//! Rendering of a parsed [`Feature`] back into Gherkin text, optionally
+//! highlighted for terminals or web pages.
+//!
+//! Rendered output always uses the canonical layout: two spaces of
+//! indentation per level, aligned tables and `"""` doc strings, in which a
+//! `"""` is escaped as `\"\"\"`. Comments are not part of the AST, and are
+//! therefore not rendered.
+
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{
+    data_table::escape_cell,
+    examples_loader::examples_source,
+    lexer::{find_placeholder, Keyword, TokenKind},
+    parser::{DOC_STRING_DELIMITER, ESCAPED_DOC_STRING_DELIMITER},
+    DataTable, Feature, Scenario, ScenarioOutline, Step, StepData, StepType, TaggedScenarios,
+};
+
+/// How the rendered output is highlighted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Plain Gherkin text.
+    Plain,
+    /// Gherkin text highlighted using ANSI escape codes.
+    Ansi,
+    /// An HTML `<pre class="gherkin">` element, where highlighted parts are
+    /// wrapped in `<span>`s with the class returned by [`css_class`].
+    Html,
+}
+
+/// The CSS class used for tokens of `kind` in [`Style::Html`] output.
+pub fn css_class(kind: TokenKind) -> &'static str {
+    match kind {
+        TokenKind::Keyword(_) => "gherkin-keyword",
+        TokenKind::Colon => "gherkin-colon",
+        TokenKind::Tag => "gherkin-tag",
+        TokenKind::Comment => "gherkin-comment",
+        TokenKind::Name => "gherkin-name",
+        TokenKind::Description => "gherkin-description",
+        TokenKind::StepText => "gherkin-step-text",
+        TokenKind::Placeholder => "gherkin-placeholder",
+        TokenKind::TableSeparator => "gherkin-table-separator",
+        TokenKind::TableCell => "gherkin-table-cell",
+        TokenKind::DocStringDelimiter => "gherkin-doc-string-delimiter",
+        TokenKind::DocStringMediaType => "gherkin-doc-string-media-type",
+        TokenKind::DocString => "gherkin-doc-string",
+    }
+}
+
+/// A default stylesheet for [`Style::Html`] output.
+pub const DEFAULT_CSS: &str = "\
+pre.gherkin { background: #f8f8f8; padding: 1em; }
+.gherkin-keyword { color: #0550ae; font-weight: bold; }
+.gherkin-tag { color: #953800; }
+.gherkin-comment { color: #6e7781; font-style: italic; }
+.gherkin-name { font-weight: bold; }
+.gherkin-description { color: #57606a; font-style: italic; }
+.gherkin-placeholder { color: #8250df; }
+.gherkin-table-separator, .gherkin-doc-string-delimiter { color: #6e7781; }
+.gherkin-table-cell, .gherkin-doc-string { color: #116329; }
+.gherkin-doc-string-media-type { color: #953800; }
+";
+
+fn ansi_code(kind: TokenKind) -> Option<&'static str> {
+    match kind {
+        TokenKind::Keyword(_) => Some("1;36"),
+        TokenKind::Tag | TokenKind::DocStringMediaType => Some("33"),
+        TokenKind::Comment | TokenKind::TableSeparator | TokenKind::DocStringDelimiter => {
+            Some("90")
+        }
+        TokenKind::Name => Some("1"),
+        TokenKind::Description => Some("3"),
+        TokenKind::Placeholder => Some("35"),
+        TokenKind::TableCell | TokenKind::DocString => Some("32"),
+        TokenKind::Colon | TokenKind::StepText => None,
+    }
+}
+
+pub(crate) fn html_escape(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+}
+
+pub(crate) fn step_keyword(ty: StepType) -> (Keyword, &'static str) {
+    match ty {
+        StepType::Given => (Keyword::Given, "Given"),
+        StepType::When => (Keyword::When, "When"),
+        StepType::Then => (Keyword::Then, "Then"),
+        StepType::And => (Keyword::And, "And"),
+        StepType::But => (Keyword::But, "But"),
+        StepType::Asterisk => (Keyword::Asterisk, "*"),
+    }
+}
+
+struct Renderer {
+    style: Style,
+    out: String,
+}
+
+impl Renderer {
+    fn token(&mut self, kind: TokenKind, text: &str) {
+        match self.style {
+            Style::Plain => self.out.push_str(text),
+            Style::Ansi => match ansi_code(kind) {
+                Some(code) => {
+                    self.out.push_str("\x1b[");
+                    self.out.push_str(code);
+                    self.out.push('m');
+                    self.out.push_str(text);
+                    self.out.push_str("\x1b[0m");
+                }
+                None => self.out.push_str(text),
+            },
+            Style::Html => {
+                self.out.push_str("<span class=\"");
+                self.out.push_str(css_class(kind));
+                self.out.push_str("\">");
+                html_escape(&mut self.out, text);
+                self.out.push_str("</span>");
+            }
+        }
+    }
+
+    /// Text that is not highlighted, such as whitespace.
+    fn plain(&mut self, text: &str) {
+        match self.style {
+            Style::Html => html_escape(&mut self.out, text),
+            Style::Plain | Style::Ansi => self.out.push_str(text),
+        }
+    }
+
+    fn indent(&mut self, level: usize) {
+        for _ in 0..level {
+            self.out.push_str("  ");
+        }
+    }
+
+    fn text_with_placeholders(&mut self, kind: TokenKind, text: &str) {
+        let mut rest = text;
+        while let Some(range) = find_placeholder(rest) {
+            if range.start != 0 {
+                self.token(kind, &rest[..range.start]);
+            }
+            self.token(TokenKind::Placeholder, &rest[range.clone()]);
+            rest = &rest[range.end..];
+        }
+
+        if !rest.is_empty() {
+            self.token(kind, rest);
+        }
+    }
+
+    fn tags(&mut self, level: usize, tags: &[String]) {
+        if tags.is_empty() {
+            return;
+        }
+
+        self.indent(level);
+        for (idx, tag) in tags.iter().enumerate() {
+            if idx != 0 {
+                self.plain(" ");
+            }
+            self.token(TokenKind::Tag, &format!("@{tag}"));
+        }
+        self.plain("\n");
+    }
+
+    fn keyword_line(&mut self, level: usize, keyword: Keyword, text: &str, name: Option<&str>) {
+        self.indent(level);
+        self.token(TokenKind::Keyword(keyword), text);
+        self.token(TokenKind::Colon, ":");
+        if let Some(name) = name {
+            self.plain(" ");
+            self.text_with_placeholders(TokenKind::Name, name);
+        }
+        self.plain("\n");
+    }
+
+    fn description(&mut self, level: usize, description: Option<&str>) {
+        let Some(description) = description else {
+            return;
+        };
+
+        for line in description.lines() {
+            if !line.trim().is_empty() {
+                self.indent(level);
+                self.token(TokenKind::Description, line);
+            }
+            self.plain("\n");
+        }
+    }
+
+    fn table(&mut self, level: usize, header: &[String], rows: &[Vec<String>]) {
+        let rows: Vec<Vec<_>> = core::iter::once(header)
+            .chain(rows.iter().map(Vec::as_slice))
+            .map(|r| r.iter().map(|c| escape_cell(c)).collect())
+            .collect();
+
+        let widths: Vec<usize> = (0..header.len())
+            .map(|col| {
+                rows.iter()
+                    .map(|r| r[col].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        for row in rows {
+            self.indent(level);
+            self.token(TokenKind::TableSeparator, "|");
+            for (cell, width) in row.iter().zip(&widths) {
+                self.plain(" ");
+                self.text_with_placeholders(TokenKind::TableCell, cell);
+                let padding = width - cell.chars().count();
+                self.plain(&" ".repeat(padding + 1));
+                self.token(TokenKind::TableSeparator, "|");
+            }
+            self.plain("\n");
+        }
+    }
+
+    fn data_table(&mut self, level: usize, table: &DataTable) {
+        self.table(level, table.header(), table.rows());
+    }
+
+    fn doc_string(&mut self, level: usize, doc_string: &str) {
+        self.indent(level);
+        self.token(TokenKind::DocStringDelimiter, DOC_STRING_DELIMITER);
+        self.plain("\n");
+        for line in doc_string.lines() {
+            if !line.is_empty() {
+                self.indent(level);
+                let line = line.replace(DOC_STRING_DELIMITER, ESCAPED_DOC_STRING_DELIMITER);
+                self.text_with_placeholders(TokenKind::DocString, &line);
+            }
+            self.plain("\n");
+        }
+        self.indent(level);
+        self.token(TokenKind::DocStringDelimiter, DOC_STRING_DELIMITER);
+        self.plain("\n");
+    }
+
+    fn steps(&mut self, level: usize, steps: &[Step]) {
+        for step in steps {
+            let (keyword, text) = step_keyword(step.ty);
+            self.indent(level);
+            self.token(TokenKind::Keyword(keyword), text);
+            self.plain(" ");
+            self.text_with_placeholders(TokenKind::StepText, &step.description);
+            self.plain("\n");
+
+            match &step.data {
+                Some(StepData::DataTable(table)) => self.data_table(level + 1, table),
+                Some(StepData::DocString(doc_string)) => self.doc_string(level + 1, doc_string),
+                None => {}
+            }
+        }
+    }
+
+    fn scenario(&mut self, level: usize, scenario: &Scenario) {
+        self.plain("\n");
+        self.tags(level, &scenario.tags);
+        self.keyword_line(
+            level,
+            Keyword::Scenario,
+            "Scenario",
+            scenario.name.as_deref(),
+        );
+        self.description(level + 1, scenario.description.as_deref());
+        self.steps(level + 1, &scenario.steps);
+    }
+
+    fn examples(&mut self, level: usize, examples: &TaggedScenarios) {
+        self.plain("\n");
+        self.tags(level, examples.tags());
+        self.keyword_line(level, Keyword::Scenarios, "Examples", examples.name());
+        self.description(level + 1, examples.description());
+        // Examples loaded from a source keep referring to it.
+        if examples_source(examples.tags()).is_none() {
+            self.table(level + 1, examples.placeholders(), examples.values());
+        }
+    }
+
+    fn scenario_outline(&mut self, level: usize, outline: &ScenarioOutline) {
+        self.plain("\n");
+        self.tags(level, &outline.tags);
+        self.keyword_line(
+            level,
+            Keyword::ScenarioOutline,
+            "Scenario Outline",
+            outline.name.as_deref(),
+        );
+        self.description(level + 1, outline.description.as_deref());
+        self.steps(level + 1, &outline.steps);
+
+        for examples in &outline.scenarios {
+            self.examples(level + 1, examples);
+        }
+    }
+
+    fn feature(&mut self, feature: &Feature) {
+        self.tags(0, &feature.tags);
+        self.keyword_line(0, Keyword::Feature, "Feature", feature.name.as_deref());
+        self.description(1, feature.description.as_deref());
+
+        if let Some(background) = &feature.background {
+            self.plain("\n");
+            self.keyword_line(
+                1,
+                Keyword::Background,
+                "Background",
+                background.name.as_deref(),
+            );
+            self.description(2, background.description.as_deref());
+            self.steps(2, &background.steps);
+        }
+
+        for scenario in &feature.scenarios {
+            self.scenario(1, scenario);
+        }
+
+        for outline in &feature.scenario_outlines {
+            self.scenario_outline(1, outline);
+        }
+    }
+}
+
+/// Render `feature` as Gherkin text in the given style.
+pub fn render(feature: &Feature, style: Style) -> String {
+    let mut renderer = Renderer {
+        style,
+        out: String::new(),
+    };
+
+    if style == Style::Html {
+        renderer.out.push_str("<pre class=\"gherkin\">");
+    }
+
+    renderer.feature(feature);
+
+    if style == Style::Html {
+        renderer.out.push_str("</pre>");
+    }
+
+    renderer.out
+}
+
+impl core::fmt::Display for Feature {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&render(self, Style::Plain))
+    }
+}
+
+#[cfg(test)]
+const FEATURE: &str = r#"@shop
+Feature: Buying <things>
+  Customers buy things.
+
+  Background: a shop
+    With a description.
+    Given a shop
+
+  Scenario: paying
+    Given the following prices
+      | item   | price |
+      | banana | 1     |
+    Then the receipt reads
+      """
+      1 banana & more
+      """
+
+  @slow
+  Scenario Outline: buying many
+    Given <count> items
+    Then I pay <count>
+
+    @small
+    Examples: small amounts
+      Only a few.
+      | count |
+      | 1     |
+"#;
+
+#[test]
+fn plain() {
+    let feature = crate::Parser::parse_feature(FEATURE).unwrap();
+
+    let rendered = render(&feature, Style::Plain);
+    assert_eq!(rendered, FEATURE);
+    assert_eq!(feature.to_string(), FEATURE);
+    assert_eq!(crate::Parser::parse_feature(&rendered).unwrap(), feature);
+}
+
+#[test]
+fn highlighted() {
+    let feature = crate::Parser::parse_feature(FEATURE).unwrap();
+
+    let ansi = render(&feature, Style::Ansi);
+    assert!(ansi.contains("\x1b[1;36mFeature\x1b[0m: \x1b[1mBuying \x1b[0m\x1b[35m<things>\x1b[0m"));
+    assert!(ansi.contains("\x1b[33m@slow\x1b[0m"));
+
+    let html = render(&feature, Style::Html);
+    assert!(html.starts_with("<pre class=\"gherkin\">"));
+    assert!(html.ends_with("</pre>"));
+    assert!(html.contains(
+        "<span class=\"gherkin-keyword\">Given</span> \
+         <span class=\"gherkin-placeholder\">&lt;count&gt;</span>\
+         <span class=\"gherkin-step-text\"> items</span>"
+    ));
+    assert!(html.contains("<span class=\"gherkin-doc-string\">1 banana &amp; more</span>"));
+}
+
+#[test]
+fn doc_string_delimiters() {
+    const FEATURE: &str = r#"Feature: quoting
+
+  Scenario: nested
+    Given the feature
+      """
+      Feature: inner
+        Scenario: s
+          Given the text
+            \"\"\"
+            a \"\"\" b
+            \"\"\"
+      """
+"#;
+
+    let feature = crate::Parser::parse_feature(FEATURE).unwrap();
+    let Some(StepData::DocString(doc_string)) = &feature.scenarios[0].steps[0].data else {
+        panic!("expected a doc string");
+    };
+    assert_eq!(
+        doc_string,
+        "Feature: inner\n  Scenario: s\n    Given the text\n      \"\"\"\n      a \"\"\" b\n      \"\"\""
+    );
+
+    let rendered = render(&feature, Style::Plain);
+    assert_eq!(rendered, FEATURE);
+    assert_eq!(crate::Parser::parse_feature(&rendered).unwrap(), feature);
+}
+
+#[test]
+fn loaded_examples() {
+    const FEATURE: &str = "Feature: prices
+
+  Scenario Outline: pricing
+    Given a <item>
+
+    @source(prices.csv)
+    Examples:
+";
+
+    let loader = |_: &str| {
+        Ok(DataTable::new_populated(vec!["item".into()], vec![vec!["banana".into()]]).unwrap())
+    };
+    let parse = |text: &str| {
+        crate::ParserOptions::strict()
+            .examples_loader(&loader)
+            .parse(text)
+    };
+    let feature = parse(FEATURE).unwrap();
+
+    let rendered = render(&feature, Style::Plain);
+    assert_eq!(rendered, FEATURE);
+    assert_eq!(parse(&rendered).unwrap(), feature);
+}
@@ -1,10 +1,17 @@
-use crate::{Scenario, Step};
+use alloc::{format, string::String, vec, vec::Vec};
+
+use crate::{Location, Scenario, Step};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct TaggedScenarios {
-    tags: Vec<String>,
-    placeholders: Vec<String>,
-    values: Vec<Vec<String>>,
+    pub(crate) tags: Vec<String>,
+    pub(crate) name: Option<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) location: Location,
+    /// The locations of the rows in `values`.
+    pub(crate) row_locations: Vec<Location>,
+    pub(crate) placeholders: Vec<String>,
+    pub(crate) values: Vec<Vec<String>>,
 }
 
 impl TaggedScenarios {
@@ -16,11 +23,18 @@ impl TaggedScenarios {
         if values.iter().all(|v| v.len() == placeholders.len()) {
             Ok(Self {
                 tags,
+                name: None,
+                description: None,
+                location: Location::default(),
+                row_locations: vec![Location::default(); values.len()],
                 placeholders,
                 values,
             })
         } else {
-            Err("".into())
+            Err(format!(
+                "Every row of the examples must have {} values",
+                placeholders.len()
+            ))
         }
     }
 
@@ -37,6 +51,80 @@ impl TaggedScenarios {
     pub fn len(&self) -> usize {
         self.values.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn tags_mut(&mut self) -> &mut Vec<String> {
+        &mut self.tags
+    }
+
+    /// The name following `Examples:`, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn name_mut(&mut self) -> &mut Option<String> {
+        &mut self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn description_mut(&mut self) -> &mut Option<String> {
+        &mut self.description
+    }
+
+    /// The location of the `Examples` keyword.
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    pub fn location_mut(&mut self) -> &mut Location {
+        &mut self.location
+    }
+
+    /// The locations of the rows of values. Rows loaded from a `@source`
+    /// are located at the `Examples` keyword.
+    pub fn row_locations(&self) -> &[Location] {
+        &self.row_locations
+    }
+
+    pub fn row_locations_mut(&mut self) -> &mut Vec<Location> {
+        &mut self.row_locations
+    }
+
+    pub fn placeholders(&self) -> &[String] {
+        &self.placeholders
+    }
+
+    pub fn values(&self) -> &[Vec<String>] {
+        &self.values
+    }
+
+    /// The `<placeholder>`s of the examples, with their values in row
+    /// `row`.
+    pub(crate) fn replacements(&self, row: usize) -> Vec<(String, &str)> {
+        replacements(&self.placeholders, &self.values[row])
+    }
+}
+
+/// The `<placeholder>`s in `placeholders`, with their values in `row`.
+pub(crate) fn replacements<'a>(
+    placeholders: &[impl AsRef<str>],
+    row: &'a [impl AsRef<str>],
+) -> Vec<(String, &'a str)> {
+    placeholders
+        .iter()
+        .zip(row)
+        .map(|(placeholder, value)| (format!("<{}>", placeholder.as_ref()), value.as_ref()))
+        .collect()
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -46,34 +134,39 @@ pub struct ScenarioOutline {
     pub description: Option<String>,
     pub steps: Vec<Step>,
     pub scenarios: Vec<TaggedScenarios>,
+    pub location: Location,
 }
 
 impl ScenarioOutline {
     pub fn scenarios(&self) -> impl Iterator<Item = Scenario> + '_ {
-        self.scenarios.iter().flat_map(|s| {
-            s.values.iter().map(|row| {
-                let steps = self.steps.clone().into_iter().map(|mut step| {
-                    row.iter().enumerate().for_each(|(idx, cell)| {
-                        let placeholder = &s.placeholders[idx];
-                        let from = &format!("<{placeholder}>");
-                        let to = cell;
-                        step.description = step.description.replace(from, to);
-
-                        if let Some(data) = &mut step.data {
-                            data.replace(from, to);
-                        }
-                    });
-                    step
-                });
-
-                Scenario {
-                    tags: s.tags.clone(),
-                    name: self.name.clone(),
-                    description: self.description.clone(),
-                    steps: steps.collect(),
+        self.scenarios
+            .iter()
+            .flat_map(|s| (0..s.values.len()).map(|row| self.expand(s, row)))
+    }
+
+    /// The scenario for row `row` of `examples`, which are examples of this
+    /// outline.
+    pub fn expand(&self, examples: &TaggedScenarios, row: usize) -> Scenario {
+        let replacements = examples.replacements(row);
+        let steps = self.steps.iter().map(|step| {
+            let mut step = step.clone();
+            for (from, to) in &replacements {
+                step.description = step.description.replace(from, to);
+
+                if let Some(data) = &mut step.data {
+                    data.replace(from, to);
                 }
-            })
-        })
+            }
+            step
+        });
+
+        Scenario {
+            tags: examples.tags.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            steps: steps.collect(),
+            location: self.location,
+        }
     }
 }
 
@@ -85,6 +178,7 @@ fn scenario_outline() {
         tags: Vec::new(),
         name: None,
         description: None,
+        location: Location::default(),
         steps: vec![
             Step::new(StepType::Given, "some <text>".into(), None),
             Step::new(
@@ -124,6 +218,7 @@ fn scenario_outline() {
             tags: Vec::new(),
             name: None,
             description: None,
+            location: Location::default(),
             steps: vec![
                 Step::new(StepType::Given, format!("some {name}"), None),
                 Step::new(
@@ -145,3 +240,38 @@ fn scenario_outline() {
 
     assert_eq!(expected, scenarios);
 }
+
+#[test]
+fn table_placeholders() {
+    use crate::{DataTable, StepData, StepType};
+
+    let table = DataTable::new_populated(
+        vec!["<column>".into()],
+        vec![vec!["<value>".into()], vec!["fixed".into()]],
+    )
+    .unwrap();
+    let outline = ScenarioOutline {
+        tags: Vec::new(),
+        name: None,
+        description: None,
+        location: Location::default(),
+        steps: vec![Step::new(
+            StepType::Given,
+            "a table".into(),
+            Some(StepData::DataTable(table)),
+        )],
+        scenarios: vec![TaggedScenarios::new(
+            Vec::new(),
+            vec!["column".into(), "value".into()],
+            vec![vec!["name".into(), "banana".into()]],
+        )
+        .unwrap()],
+    };
+
+    let scenario = outline.scenarios().next().unwrap();
+    let Some(StepData::DataTable(table)) = &scenario.steps[0].data else {
+        panic!("expected a table");
+    };
+    assert_eq!(table.header(), &["name"]);
+    assert_eq!(table.rows(), &[vec!["banana"], vec!["fixed"]]);
+}
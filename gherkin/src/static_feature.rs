@@ -0,0 +1,31 @@
+use crate::Feature;
+
+/// A feature embedded at compile time by `include_feature!`.
+///
+/// It is const-constructible, so it can be stored in a `static` without any
+/// lazy initialization. The [`Feature`] itself is built by [`feature`].
+///
+/// [`feature`]: StaticFeature::feature
+#[derive(Debug, Clone, Copy)]
+pub struct StaticFeature {
+    path: &'static str,
+    build: fn() -> Feature,
+}
+
+impl StaticFeature {
+    /// A feature read from `path` that is built by `build`. Used by the code
+    /// that `include_feature!` expands to.
+    pub const fn new(path: &'static str, build: fn() -> Feature) -> Self {
+        Self { path, build }
+    }
+
+    /// The path of the feature file, as given to `include_feature!`.
+    pub fn path(&self) -> &'static str {
+        self.path
+    }
+
+    /// Build the feature. Each call allocates a new [`Feature`].
+    pub fn feature(&self) -> Feature {
+        (self.build)()
+    }
+}
@@ -0,0 +1,194 @@
+//! Statistics about a corpus of features, such as the amount of scenarios,
+//! how often tags are used and which step texts are reused most.
+//!
+//! ```
+//! use gherkin::{stats::Stats, Parser};
+//!
+//! let feature = Parser::parse_feature(
+//!     "Feature: f\n  @smoke\n  Scenario: s\n    Given a shop\n    Then a receipt\n",
+//! )
+//! .unwrap();
+//!
+//! let mut stats = Stats::default();
+//! stats.add("f.feature", &feature);
+//!
+//! assert_eq!(stats.scenarios, 1);
+//! assert_eq!(stats.steps, 2);
+//! assert_eq!(stats.most_used_tags(10), [("smoke", 1)]);
+//! ```
+
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::{
+    visit::{self, Visit},
+    Feature, Location, Scenario, ScenarioOutline, Step, TaggedScenarios,
+};
+
+/// The amount of steps of a scenario or scenario outline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ScenarioLength {
+    /// The file the scenario is in, as passed to [`Stats::add`].
+    pub file: String,
+    pub name: Option<String>,
+    pub location: Location,
+    /// The amount of steps, excluding those of the background.
+    pub steps: usize,
+}
+
+/// Counts collected from any number of features.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub features: usize,
+    pub scenarios: usize,
+    pub scenario_outlines: usize,
+    /// The rows of all examples of all scenario outlines, which is the
+    /// amount of scenarios the outlines expand to.
+    pub outline_rows: usize,
+    /// The steps as written, including those of backgrounds and scenario
+    /// outlines.
+    pub steps: usize,
+    /// How often each tag is used, on any element.
+    pub tags: BTreeMap<String, usize>,
+    /// How often each step text is written, regardless of its keyword.
+    pub step_texts: BTreeMap<String, usize>,
+    /// The length of every scenario and scenario outline.
+    pub scenario_lengths: Vec<ScenarioLength>,
+}
+
+impl Stats {
+    /// Add the statistics of `feature`, read from `file`.
+    pub fn add(&mut self, file: &str, feature: &Feature) {
+        Collector { stats: self, file }.visit_feature(feature);
+    }
+
+    /// The `n` most used tags, most used first.
+    pub fn most_used_tags(&self, n: usize) -> Vec<(&str, usize)> {
+        most_common(&self.tags, n)
+    }
+
+    /// The `n` most written step texts, most written first.
+    pub fn most_reused_steps(&self, n: usize) -> Vec<(&str, usize)> {
+        most_common(&self.step_texts, n)
+    }
+
+    /// The `n` scenarios and scenario outlines with the most steps, longest
+    /// first.
+    pub fn longest_scenarios(&self, n: usize) -> Vec<&ScenarioLength> {
+        let mut lengths: Vec<_> = self.scenario_lengths.iter().collect();
+        lengths.sort_by_key(|l| core::cmp::Reverse(l.steps));
+        lengths.truncate(n);
+        lengths
+    }
+}
+
+/// The `n` keys with the highest counts. Ties are ordered by key.
+fn most_common(counts: &BTreeMap<String, usize>, n: usize) -> Vec<(&str, usize)> {
+    let mut counts: Vec<_> = counts.iter().map(|(k, v)| (k.as_str(), *v)).collect();
+    counts.sort_by_key(|(_, count)| core::cmp::Reverse(*count));
+    counts.truncate(n);
+    counts
+}
+
+struct Collector<'a> {
+    stats: &'a mut Stats,
+    file: &'a str,
+}
+
+impl Collector<'_> {
+    fn add_length(&mut self, name: &Option<String>, location: Location, steps: usize) {
+        self.stats.scenario_lengths.push(ScenarioLength {
+            file: self.file.to_string(),
+            name: name.clone(),
+            location,
+            steps,
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for Collector<'_> {
+    fn visit_feature(&mut self, feature: &'ast Feature) {
+        self.stats.features += 1;
+        visit::walk_feature(self, feature);
+    }
+
+    fn visit_tag(&mut self, tag: &'ast str) {
+        *self.stats.tags.entry(tag.to_string()).or_default() += 1;
+    }
+
+    fn visit_scenario(&mut self, scenario: &'ast Scenario) {
+        self.stats.scenarios += 1;
+        self.add_length(&scenario.name, scenario.location, scenario.steps.len());
+        visit::walk_scenario(self, scenario);
+    }
+
+    fn visit_scenario_outline(&mut self, outline: &'ast ScenarioOutline) {
+        self.stats.scenario_outlines += 1;
+        self.add_length(&outline.name, outline.location, outline.steps.len());
+        visit::walk_scenario_outline(self, outline);
+    }
+
+    fn visit_examples(&mut self, examples: &'ast TaggedScenarios) {
+        self.stats.outline_rows += examples.len();
+        visit::walk_examples(self, examples);
+    }
+
+    fn visit_step(&mut self, step: &'ast Step) {
+        self.stats.steps += 1;
+        *self
+            .stats
+            .step_texts
+            .entry(step.description.clone())
+            .or_default() += 1;
+    }
+}
+
+#[test]
+fn stats() {
+    let feature = crate::Parser::parse_feature(
+        r#"@shop
+Feature: Buying
+  Background:
+    Given a shop
+
+  @smoke
+  Scenario: paying
+    Given a shop
+    When I pay
+    Then I get a receipt
+
+  @smoke @slow
+  Scenario Outline: buying many
+    When I buy <count> items
+
+    @small
+    Examples:
+      | count |
+      | 1     |
+      | 2     |
+"#,
+    )
+    .unwrap();
+
+    let mut stats = Stats::default();
+    stats.add("a.feature", &feature);
+    stats.add("b.feature", &feature);
+
+    assert_eq!(stats.features, 2);
+    assert_eq!(stats.scenarios, 2);
+    assert_eq!(stats.scenario_outlines, 2);
+    assert_eq!(stats.outline_rows, 4);
+    assert_eq!(stats.steps, 10);
+    assert_eq!(stats.most_used_tags(2), [("smoke", 4), ("shop", 2)]);
+    assert_eq!(stats.most_reused_steps(1), [("a shop", 4)]);
+
+    let longest = stats.longest_scenarios(1);
+    assert_eq!(longest[0].file, "a.feature");
+    assert_eq!(longest[0].name.as_deref(), Some("paying"));
+    assert_eq!(longest[0].location, Location::new(7, 3));
+    assert_eq!(longest[0].steps, 3);
+}
@@ -0,0 +1,73 @@
+use alloc::string::String;
+
+/// A tag split into a name and an optional value.
+///
+/// Tags are stored as written, without the leading `@`. A tag may carry a
+/// value in parentheses, as in `@owner(team-x)`, or after an equals sign,
+/// as in `@timeout=30`.
+///
+/// ```
+/// use gherkin::Tag;
+///
+/// assert_eq!(Tag::parse("owner(team-x)"), Tag { name: "owner", value: Some("team-x") });
+/// assert_eq!(Tag::parse("timeout=30"), Tag { name: "timeout", value: Some("30") });
+/// assert_eq!(Tag::parse("slow"), Tag { name: "slow", value: None });
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tag<'a> {
+    pub name: &'a str,
+    pub value: Option<&'a str>,
+}
+
+impl<'a> Tag<'a> {
+    /// Split `tag`, which does not include the leading `@`, into its name
+    /// and value.
+    pub fn parse(tag: &'a str) -> Self {
+        let parenthesized = tag
+            .strip_suffix(')')
+            .and_then(|t| t.split_once('('))
+            .filter(|(name, _)| !name.is_empty());
+        let assigned = || tag.split_once('=').filter(|(name, _)| !name.is_empty());
+
+        match parenthesized.or_else(assigned) {
+            Some((name, value)) => Self {
+                name,
+                value: Some(value),
+            },
+            None => Self {
+                name: tag,
+                value: None,
+            },
+        }
+    }
+
+    /// Find the first of `tags` named `name`.
+    pub fn find(tags: &'a [String], name: &str) -> Option<Self> {
+        tags.iter().map(|t| Tag::parse(t)).find(|t| t.name == name)
+    }
+}
+
+#[test]
+fn tags() {
+    assert_eq!(
+        Tag::parse("a(b)=c"),
+        Tag {
+            name: "a(b)",
+            value: Some("c")
+        }
+    );
+    assert_eq!(
+        Tag::parse("(b)"),
+        Tag {
+            name: "(b)",
+            value: None
+        }
+    );
+
+    let tags = ["slow".to_string(), "owner(team-x)".to_string()];
+    assert_eq!(
+        Tag::find(&tags, "owner").and_then(|t| t.value),
+        Some("team-x")
+    );
+    assert_eq!(Tag::find(&tags, "team-x"), None);
+}
@@ -0,0 +1,416 @@
+//! Traversal of a [`Feature`] AST.
+//!
+//! [`Visit`] walks a feature by reference, and [`VisitMut`] by mutable
+//! reference. Every method has a default implementation that calls the
+//! matching `walk_*` function, which visits the children of the node. An
+//! implementation that overrides a method can call the `walk_*` function
+//! itself to keep descending into the children.
+//!
+//! ```
+//! use gherkin::{visit::Visit, Step};
+//!
+//! #[derive(Default)]
+//! struct StepCounter(usize);
+//!
+//! impl<'ast> Visit<'ast> for StepCounter {
+//!     fn visit_step(&mut self, step: &'ast Step) {
+//!         self.0 += 1;
+//!         gherkin::visit::walk_step(self, step);
+//!     }
+//! }
+//! ```
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    Background, DataTable, Feature, Scenario, ScenarioOutline, Step, StepData, TaggedScenarios,
+};
+
+pub trait Visit<'ast> {
+    fn visit_feature(&mut self, feature: &'ast Feature) {
+        walk_feature(self, feature);
+    }
+
+    /// A tag, without the leading `@`.
+    fn visit_tag(&mut self, _tag: &'ast str) {}
+
+    /// The tags of a node.
+    fn visit_tags(&mut self, tags: &'ast [String]) {
+        walk_tags(self, tags);
+    }
+
+    /// The name of a node, visited even if the node has none.
+    fn visit_name(&mut self, _name: Option<&'ast str>) {}
+
+    /// The description of a node, visited even if the node has none.
+    fn visit_description(&mut self, _description: Option<&'ast str>) {}
+
+    fn visit_background(&mut self, background: &'ast Background) {
+        walk_background(self, background);
+    }
+
+    fn visit_scenario(&mut self, scenario: &'ast Scenario) {
+        walk_scenario(self, scenario);
+    }
+
+    fn visit_scenario_outline(&mut self, outline: &'ast ScenarioOutline) {
+        walk_scenario_outline(self, outline);
+    }
+
+    fn visit_examples(&mut self, examples: &'ast TaggedScenarios) {
+        walk_examples(self, examples);
+    }
+
+    fn visit_step(&mut self, step: &'ast Step) {
+        walk_step(self, step);
+    }
+
+    /// The text of a step, without its keyword.
+    fn visit_step_text(&mut self, _text: &'ast str) {}
+
+    fn visit_step_data(&mut self, data: &'ast StepData) {
+        walk_step_data(self, data);
+    }
+
+    fn visit_doc_string(&mut self, _doc_string: &'ast str) {}
+
+    fn visit_data_table(&mut self, table: &'ast DataTable) {
+        walk_data_table(self, table);
+    }
+
+    /// A cell of a data table or of the table of an `Examples` section,
+    /// including header cells.
+    fn visit_table_cell(&mut self, _cell: &'ast str) {}
+}
+
+pub fn walk_feature<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, feature: &'ast Feature) {
+    v.visit_tags(&feature.tags);
+    v.visit_name(feature.name.as_deref());
+    v.visit_description(feature.description.as_deref());
+    if let Some(background) = &feature.background {
+        v.visit_background(background);
+    }
+    feature.scenarios.iter().for_each(|s| v.visit_scenario(s));
+    feature
+        .scenario_outlines
+        .iter()
+        .for_each(|o| v.visit_scenario_outline(o));
+}
+
+pub fn walk_tags<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, tags: &'ast [String]) {
+    tags.iter().for_each(|t| v.visit_tag(t));
+}
+
+pub fn walk_background<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, background: &'ast Background) {
+    v.visit_name(background.name.as_deref());
+    v.visit_description(background.description.as_deref());
+    background.steps.iter().for_each(|s| v.visit_step(s));
+}
+
+pub fn walk_scenario<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, scenario: &'ast Scenario) {
+    v.visit_tags(&scenario.tags);
+    v.visit_name(scenario.name.as_deref());
+    v.visit_description(scenario.description.as_deref());
+    scenario.steps.iter().for_each(|s| v.visit_step(s));
+}
+
+pub fn walk_scenario_outline<'ast, V: Visit<'ast> + ?Sized>(
+    v: &mut V,
+    outline: &'ast ScenarioOutline,
+) {
+    v.visit_tags(&outline.tags);
+    v.visit_name(outline.name.as_deref());
+    v.visit_description(outline.description.as_deref());
+    outline.steps.iter().for_each(|s| v.visit_step(s));
+    outline.scenarios.iter().for_each(|e| v.visit_examples(e));
+}
+
+pub fn walk_examples<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, examples: &'ast TaggedScenarios) {
+    v.visit_tags(&examples.tags);
+    v.visit_name(examples.name.as_deref());
+    v.visit_description(examples.description.as_deref());
+    examples
+        .placeholders
+        .iter()
+        .chain(examples.values.iter().flatten())
+        .for_each(|c| v.visit_table_cell(c));
+}
+
+pub fn walk_step<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, step: &'ast Step) {
+    v.visit_step_text(&step.description);
+    if let Some(data) = &step.data {
+        v.visit_step_data(data);
+    }
+}
+
+pub fn walk_step_data<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, data: &'ast StepData) {
+    match data {
+        StepData::DocString(doc_string) => v.visit_doc_string(doc_string),
+        StepData::DataTable(table) => v.visit_data_table(table),
+    }
+}
+
+pub fn walk_data_table<'ast, V: Visit<'ast> + ?Sized>(v: &mut V, table: &'ast DataTable) {
+    table
+        .header
+        .iter()
+        .chain(table.rows.iter().flatten())
+        .for_each(|c| v.visit_table_cell(c));
+}
+
+pub trait VisitMut {
+    fn visit_feature_mut(&mut self, feature: &mut Feature) {
+        walk_feature_mut(self, feature);
+    }
+
+    /// A tag, without the leading `@`.
+    fn visit_tag_mut(&mut self, _tag: &mut String) {}
+
+    /// The tags of a node. Override this method to add or remove tags.
+    fn visit_tags_mut(&mut self, tags: &mut Vec<String>) {
+        walk_tags_mut(self, tags);
+    }
+
+    /// The name of a node, visited even if the node has none.
+    fn visit_name_mut(&mut self, _name: &mut Option<String>) {}
+
+    /// The description of a node, visited even if the node has none.
+    fn visit_description_mut(&mut self, _description: &mut Option<String>) {}
+
+    fn visit_background_mut(&mut self, background: &mut Background) {
+        walk_background_mut(self, background);
+    }
+
+    fn visit_scenario_mut(&mut self, scenario: &mut Scenario) {
+        walk_scenario_mut(self, scenario);
+    }
+
+    fn visit_scenario_outline_mut(&mut self, outline: &mut ScenarioOutline) {
+        walk_scenario_outline_mut(self, outline);
+    }
+
+    fn visit_examples_mut(&mut self, examples: &mut TaggedScenarios) {
+        walk_examples_mut(self, examples);
+    }
+
+    fn visit_step_mut(&mut self, step: &mut Step) {
+        walk_step_mut(self, step);
+    }
+
+    /// The text of a step, without its keyword.
+    fn visit_step_text_mut(&mut self, _text: &mut String) {}
+
+    fn visit_step_data_mut(&mut self, data: &mut StepData) {
+        walk_step_data_mut(self, data);
+    }
+
+    fn visit_doc_string_mut(&mut self, _doc_string: &mut String) {}
+
+    fn visit_data_table_mut(&mut self, table: &mut DataTable) {
+        walk_data_table_mut(self, table);
+    }
+
+    /// A cell of a data table or of the table of an `Examples` section,
+    /// including header cells.
+    fn visit_table_cell_mut(&mut self, _cell: &mut String) {}
+}
+
+pub fn walk_feature_mut<V: VisitMut + ?Sized>(v: &mut V, feature: &mut Feature) {
+    v.visit_tags_mut(&mut feature.tags);
+    v.visit_name_mut(&mut feature.name);
+    v.visit_description_mut(&mut feature.description);
+    if let Some(background) = &mut feature.background {
+        v.visit_background_mut(background);
+    }
+    feature
+        .scenarios
+        .iter_mut()
+        .for_each(|s| v.visit_scenario_mut(s));
+    feature
+        .scenario_outlines
+        .iter_mut()
+        .for_each(|o| v.visit_scenario_outline_mut(o));
+}
+
+pub fn walk_tags_mut<V: VisitMut + ?Sized>(v: &mut V, tags: &mut [String]) {
+    tags.iter_mut().for_each(|t| v.visit_tag_mut(t));
+}
+
+pub fn walk_background_mut<V: VisitMut + ?Sized>(v: &mut V, background: &mut Background) {
+    v.visit_name_mut(&mut background.name);
+    v.visit_description_mut(&mut background.description);
+    background
+        .steps
+        .iter_mut()
+        .for_each(|s| v.visit_step_mut(s));
+}
+
+pub fn walk_scenario_mut<V: VisitMut + ?Sized>(v: &mut V, scenario: &mut Scenario) {
+    v.visit_tags_mut(&mut scenario.tags);
+    v.visit_name_mut(&mut scenario.name);
+    v.visit_description_mut(&mut scenario.description);
+    scenario.steps.iter_mut().for_each(|s| v.visit_step_mut(s));
+}
+
+pub fn walk_scenario_outline_mut<V: VisitMut + ?Sized>(v: &mut V, outline: &mut ScenarioOutline) {
+    v.visit_tags_mut(&mut outline.tags);
+    v.visit_name_mut(&mut outline.name);
+    v.visit_description_mut(&mut outline.description);
+    outline.steps.iter_mut().for_each(|s| v.visit_step_mut(s));
+    outline
+        .scenarios
+        .iter_mut()
+        .for_each(|e| v.visit_examples_mut(e));
+}
+
+pub fn walk_examples_mut<V: VisitMut + ?Sized>(v: &mut V, examples: &mut TaggedScenarios) {
+    v.visit_tags_mut(&mut examples.tags);
+    v.visit_name_mut(&mut examples.name);
+    v.visit_description_mut(&mut examples.description);
+    examples
+        .placeholders
+        .iter_mut()
+        .chain(examples.values.iter_mut().flatten())
+        .for_each(|c| v.visit_table_cell_mut(c));
+}
+
+pub fn walk_step_mut<V: VisitMut + ?Sized>(v: &mut V, step: &mut Step) {
+    v.visit_step_text_mut(&mut step.description);
+    if let Some(data) = &mut step.data {
+        v.visit_step_data_mut(data);
+    }
+}
+
+pub fn walk_step_data_mut<V: VisitMut + ?Sized>(v: &mut V, data: &mut StepData) {
+    match data {
+        StepData::DocString(doc_string) => v.visit_doc_string_mut(doc_string),
+        StepData::DataTable(table) => v.visit_data_table_mut(table),
+    }
+}
+
+pub fn walk_data_table_mut<V: VisitMut + ?Sized>(v: &mut V, table: &mut DataTable) {
+    table
+        .header
+        .iter_mut()
+        .chain(table.rows.iter_mut().flatten())
+        .for_each(|c| v.visit_table_cell_mut(c));
+}
+
+#[cfg(test)]
+const FEATURE: &str = r#"
+@shop
+Feature: Buying
+    Background:
+        Given a shop
+
+    @fast
+    Scenario: paying
+        Given the following prices
+            | item   | price |
+            | banana | 1     |
+        Then the receipt reads
+            """
+            1 banana
+            """
+
+    Scenario Outline: buying many
+        Given <count> items
+
+        @small
+        Examples:
+            | count |
+            | 1     |
+"#;
+
+#[test]
+fn visit() {
+    #[derive(Default)]
+    struct Counter<'ast> {
+        tags: Vec<&'ast str>,
+        names: usize,
+        steps: usize,
+        step_texts: Vec<&'ast str>,
+        cells: usize,
+        doc_strings: usize,
+    }
+
+    impl<'ast> Visit<'ast> for Counter<'ast> {
+        fn visit_tag(&mut self, tag: &'ast str) {
+            self.tags.push(tag);
+        }
+
+        fn visit_name(&mut self, name: Option<&'ast str>) {
+            self.names += usize::from(name.is_some());
+        }
+
+        fn visit_step(&mut self, step: &'ast Step) {
+            self.steps += 1;
+            walk_step(self, step);
+        }
+
+        fn visit_step_text(&mut self, text: &'ast str) {
+            self.step_texts.push(text);
+        }
+
+        fn visit_doc_string(&mut self, _doc_string: &'ast str) {
+            self.doc_strings += 1;
+        }
+
+        fn visit_table_cell(&mut self, _cell: &'ast str) {
+            self.cells += 1;
+        }
+    }
+
+    let feature = crate::Parser::parse_feature(FEATURE).unwrap();
+    let mut counter = Counter::default();
+    counter.visit_feature(&feature);
+
+    assert_eq!(counter.tags, ["shop", "fast", "small"]);
+    assert_eq!(counter.names, 3);
+    assert_eq!(counter.steps, 4);
+    assert_eq!(
+        counter.step_texts,
+        [
+            "a shop",
+            "the following prices",
+            "the receipt reads",
+            "<count> items"
+        ]
+    );
+    assert_eq!(counter.cells, 6);
+    assert_eq!(counter.doc_strings, 1);
+}
+
+#[test]
+fn visit_mut() {
+    struct TagRewriter;
+
+    impl VisitMut for TagRewriter {
+        fn visit_tags_mut(&mut self, tags: &mut Vec<String>) {
+            tags.retain(|t| t != "fast");
+            walk_tags_mut(self, tags);
+        }
+
+        fn visit_tag_mut(&mut self, tag: &mut String) {
+            tag.make_ascii_uppercase();
+        }
+
+        fn visit_name_mut(&mut self, name: &mut Option<String>) {
+            name.get_or_insert_with(|| "unnamed".into());
+        }
+
+        fn visit_step_text_mut(&mut self, text: &mut String) {
+            *text = text.replace("shop", "store");
+        }
+    }
+
+    let mut feature = crate::Parser::parse_feature(FEATURE).unwrap();
+    TagRewriter.visit_feature_mut(&mut feature);
+
+    assert_eq!(feature.tags, ["SHOP"]);
+    assert!(feature.scenarios[0].tags.is_empty());
+    assert_eq!(feature.scenario_outlines[0].scenarios[0].tags(), ["SMALL"]);
+    let background = feature.background.unwrap();
+    assert_eq!(background.name.as_deref(), Some("unnamed"));
+    assert_eq!(background.steps[0].description, "a store");
+}
@@ -0,0 +1,36 @@
+//! Checks that `gherkin` builds without `std`.
+//!
+//! This crate defines a `#[panic_handler]`, which fails to compile with
+//! "duplicate lang item" if `std` is linked in by any dependency. Build it
+//! with:
+//!
+//! ```sh
+//! cargo build --manifest-path no-std-check/Cargo.toml
+//! ```
+//!
+//! With the target installed, it can also be built for a bare-metal target,
+//! for instance with `--target thumbv7em-none-eabi`.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+use core::panic::PanicInfo;
+
+use gherkin::{lint, render, Feature, Parser};
+
+#[panic_handler]
+fn panic(_: &PanicInfo) -> ! {
+    loop {}
+}
+
+/// Parse, lint and render `input`, using the parts of `gherkin` that an
+/// embedded runner needs.
+pub fn check(input: &str) -> Option<(Feature, usize, String)> {
+    let feature = Parser::parse_feature(input).ok()?;
+    let diagnostics = lint::lint(input, &lint::LintConfig::default()).len();
+    let rendered = render::render(&feature, render::Style::Plain);
+
+    Some((feature, diagnostics, rendered))
+}